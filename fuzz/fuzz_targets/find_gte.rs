@@ -0,0 +1,27 @@
+//! Checks lookups against a linear scan. Run with `cargo +nightly fuzz run find_gte`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ordsearch::OrderedCollection;
+
+// the first byte picks how many of the values are elements, and the rest are queries. values are
+// taken modulo a small range so that duplicates and exact matches are common.
+fuzz_target!(|data: &[u8]| {
+    let (split, rest) = match data.split_first() {
+        Some((&split, rest)) => (split as usize, rest),
+        None => return,
+    };
+    let values: Vec<u32> = rest
+        .chunks(2)
+        .map(|c| c.iter().fold(0u32, |v, &b| v << 8 | u32::from(b)) % 512)
+        .collect();
+    let (elements, queries) = values.split_at(split.min(values.len()));
+
+    let c = OrderedCollection::from(elements.to_vec());
+    assert_eq!(c.len(), elements.len());
+    for &q in queries.iter().chain(elements).chain(&[0, 511, u32::MAX]) {
+        let expected = elements.iter().filter(|&&e| e >= q).min();
+        assert_eq!(c.find_gte(q), expected);
+    }
+});
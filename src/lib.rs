@@ -23,6 +23,17 @@
 //! Note that prefetching is *only* enabled with the (non-default) `nightly` feature due to
 //! https://github.com/aweinstock314/prefetch/issues/1. Suggestions for workarounds welcome.
 //!
+//! Lookups index into the search layout without bounds checks. The (non-default) `safe` feature
+//! replaces these with checked indexing, at some cost to lookup speed. To see what the unchecked
+//! indexing buys on your machine, compare the benchmark results with `--features nightly` to those
+//! with `--features nightly,safe`.
+//!
+//! The (non-default, nightly-only) `allocator_api` feature makes `OrderedCollection` generic over
+//! an allocator `A` (defaulting to `Global`), with `from_vec_in` and `from_sorted_iter_in` to build
+//! the search layout in a caller-supplied allocator.
+//!
+//! The minimum supported Rust version is 1.73, as declared by `rust-version` in `Cargo.toml`.
+//!
 //! # Performance
 //!
 //! The included benchmarks can be run with
@@ -33,9 +44,17 @@
 //!
 //! This will benchmark both construction and search with different number of values, and
 //! differently sized values -- look for the line that aligns closest with your data. The general
-//! trend is that `ordsearch` is faster when `n` is smaller and `T` is larger. You may also want to
-//! compare with the pending Rust PR "[Improve SliceExt::binary_search
-//! performance](https://github.com/rust-lang/rust/pull/45333)".
+//! trend is that `ordsearch` is faster when `n` is smaller and `T` is larger. The benchmarks also
+//! include a search using `slice::partition_point`, which is the standard library's lower-bound
+//! primitive, and which has since incorporated the Rust PR "[Improve SliceExt::binary_search
+//! performance](https://github.com/rust-lang/rust/pull/45333)". The `partition_point_black_box`
+//! benchmarks run the same search with the slice and query passed through `black_box`, so that
+//! the standard library search gets no more help from the optimizer than `ordsearch` does.
+//! Queries are drawn uniformly from the range of values, except in the `_zipf` benchmarks, where
+//! the `k`-th smallest value is queried with probability roughly proportional to `1 / k`.
+//! The `oneshot` benchmarks build a collection from unsorted values and then issue a single
+//! lookup, which is the cost that matters for a table that is built to answer one query; for
+//! `sorted_vec`, that is a sort followed by one `binary_search`.
 //! [Summarized](https://github.com/BurntSushi/cargo-benchcmp) results from my laptop (an X1 Carbon
 //! with i7-5600U @ 2.60GHz) are given below.
 //!
@@ -87,20 +106,56 @@
 //! -usize::l3_dup  241               614                    373  154.77%   x 0.39
 //! ```
 //!
+//! All of the above fit in the last-level cache. The `large` benchmarks instead search 100M
+//! `u32`s, which makes every lookup wait on TLB misses and main memory. They need about a gigabyte
+//! of memory and take a while to set up, so they do nothing unless `ORDSEARCH_BENCH_LARGE` is set:
+//!
+//! ```console,ignore
+//! $ ORDSEARCH_BENCH_LARGE=1 cargo +nightly bench --features nightly large
+//! ```
+//!
 //! # Future work
 //!
 //!  - [ ] Implement aligned operation: https://github.com/patmorin/arraylayout/blob/3f20174a2a0ab52c6f37f2ea87d087307f19b5ee/src/eytzinger_array.h#L204
-//!  - [ ] Implement deep prefetching for large `T`: https://github.com/patmorin/arraylayout/blob/3f20174a2a0ab52c6f37f2ea87d087307f19b5ee/src/eytzinger_array.h#L128
 //!
 #![deny(missing_docs)]
 #![cfg_attr(feature = "nightly", feature(test))]
 #![cfg_attr(feature = "nightly", feature(concat_idents))]
+#![cfg_attr(feature = "nightly", feature(portable_simd))]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+#[cfg(all(test, feature = "async"))]
+extern crate futures;
+#[cfg(feature = "async")]
+extern crate futures_util;
 #[cfg(feature = "nightly")]
 extern crate prefetch;
+#[cfg(feature = "rayon")]
+extern crate rayon;
 #[cfg(feature = "nightly")]
 extern crate test;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+
+#[cfg(feature = "async")]
+use futures_util::Stream;
 
+#[cfg(feature = "allocator_api")]
+use std::alloc::{Allocator, Global};
 use std::borrow::Borrow;
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, TryReserveError};
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "async")]
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::iter;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Bound, Deref, Index, RangeBounds, Sub};
+use std::ptr;
+use std::sync::OnceLock;
 
 /// A collection of ordered items that can efficiently satisfy queries for nearby elements.
 ///
@@ -119,10 +174,42 @@ use std::borrow::Borrow;
 /// assert_eq!(x.find_gte(64), Some(&64));
 /// assert_eq!(x.find_gte(65), None);
 /// ```
-pub struct OrderedCollection<T> {
+pub struct OrderedCollection<T, #[cfg(feature = "allocator_api")] A: Allocator = Global> {
+    #[cfg(not(feature = "allocator_api"))]
     items: Vec<T>,
+    #[cfg(feature = "allocator_api")]
+    items: Vec<T, A>,
 
-    #[cfg(feature = "nightly")] mask: usize,
+    // the smallest all-ones bit pattern that covers every index into `items`. prefetching uses it
+    // to keep prefetched addresses within the array. it is kept even when prefetching is disabled
+    // so that the layout of the struct doesn't depend on the enabled features.
+    #[cfg_attr(not(feature = "nightly"), allow(dead_code))]
+    mask: usize,
+
+    // how far past the start of the cache line of descendants to prefetch, in elements.
+    #[cfg_attr(not(feature = "nightly"), allow(dead_code))]
+    offset: usize,
+
+    // whether lookups prefetch at all.
+    #[cfg_attr(not(feature = "nightly"), allow(dead_code))]
+    prefetch: bool,
+}
+
+// implement the methods in the body for collections with any allocator when the (nightly-only)
+// `allocator_api` feature is enabled, and for collections with the global allocator otherwise.
+// this is used for the lookups and the other methods that work the same for any allocator.
+macro_rules! any_alloc {
+    (impl<T $(: $bound:path)?> { $($body:tt)* }) => {
+        #[cfg(feature = "allocator_api")]
+        impl<T $(: $bound)?, A: Allocator> OrderedCollection<T, A> {
+            $($body)*
+        }
+
+        #[cfg(not(feature = "allocator_api"))]
+        impl<T $(: $bound)?> OrderedCollection<T> {
+            $($body)*
+        }
+    };
 }
 
 impl<T: Ord> From<Vec<T>> for OrderedCollection<T> {
@@ -137,268 +224,6267 @@ impl<T: Ord> From<Vec<T>> for OrderedCollection<T> {
     /// ```
     fn from(mut v: Vec<T>) -> OrderedCollection<T> {
         v.sort_unstable();
-        Self::from_sorted_iter(v.into_iter())
+        Self::from_sorted_iter(v)
     }
 }
 
-/// Insert items from the sorted iterator `iter` into `v` in complete binary tree order.
-///
-/// Requires `iter` to be a sorted iterator.
-/// Requires v's capacity to be set to the number of elements in `iter`.
-/// The length of `v` will not be changed by this function.
-fn eytzinger_walk<I, T>(v: &mut Vec<T>, iter: &mut I, i: usize)
-where
-    I: Iterator<Item = T>,
-{
-    if i >= v.capacity() {
-        return;
+impl<T: PartialEq> PartialEq for OrderedCollection<T> {
+    /// Two collections are equal if they hold equal elements, with equal multiplicity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::from(vec![3, 1, 2]);
+    /// let b = OrderedCollection::from(vec![1, 2, 3]);
+    /// assert!(a == b);
+    /// ```
+    fn eq(&self, other: &Self) -> bool {
+        // the layout is fully determined by the sorted order of the elements, so collections with
+        // equal elements also have equal layouts.
+        self.items == other.items
     }
-
-    // visit left child
-    eytzinger_walk(v, iter, 2 * i + 1);
-
-    // put data at the root
-    // we know the get_unchecked_mut and unwrap below are safe because we set the Vec's capacity to
-    // the length of the iterator.
-    *unsafe { v.get_unchecked_mut(i) } = iter.next().unwrap();
-
-    // visit right child
-    eytzinger_walk(v, iter, 2 * i + 2);
 }
 
-impl<T: Ord> OrderedCollection<T> {
-    /// Construct a new `OrderedCollection` from an iterator over sorted elements.
-    ///
-    /// Note that if the iterator is *not* sorted, no error will be given, but lookups will give
-    /// incorrect results. The given iterator must also implement `ExactSizeIterator` so that we
-    /// know the size of the lookup array.
+impl<T: Eq> Eq for OrderedCollection<T> {}
+
+// `Ord` is deliberately not implemented, since `Ord::min` and `Ord::max` would take precedence
+// over the inherent `min` and `max` methods.
+impl<T: PartialOrd> PartialOrd for OrderedCollection<T> {
+    /// Compare the elements of two collections lexicographically, in sorted order.
     ///
     /// # Examples
     ///
-    /// Using an already-sorted iterator:
-    ///
     /// ```
-    /// # use std::collections::BTreeSet;
     /// # use ordsearch::OrderedCollection;
-    ///
-    /// let mut s = BTreeSet::new();
-    /// s.insert(42);
-    /// s.insert(89);
-    /// s.insert(7);
-    /// s.insert(12);
-    /// let a = OrderedCollection::from_sorted_iter(s);
-    /// assert_eq!(a.find_gte(50), Some(&89));
+    /// let a = OrderedCollection::from(vec![2, 1]);
+    /// let b = OrderedCollection::from(vec![3, 1]);
+    /// assert!(a < b);
     /// ```
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        InOrder::new(&self.items).partial_cmp(InOrder::new(&other.items))
+    }
+}
+
+impl<T: Hash> Hash for OrderedCollection<T> {
+    /// Hash the elements of the collection in sorted order.
     ///
-    /// Sorting a collection and then iterating (in this case, you'd likely use `new` instead):
+    /// Collections that compare as equal hash equally, regardless of the order of the elements
+    /// they were constructed from.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.items.len());
+        for e in InOrder::new(&self.items) {
+            e.hash(state);
+        }
+    }
+}
+
+impl<T> Index<usize> for OrderedCollection<T> {
+    type Output = T;
+
+    /// Get the element at position `rank` in sorted order.
+    ///
+    /// Note that the index is the position of the element in sorted order, not its position in
+    /// the underlying search layout.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rank` is out of bounds.
+    ///
+    /// # Examples
     ///
     /// ```
     /// # use ordsearch::OrderedCollection;
-    /// let mut v = vec![42, 89, 7, 12];
-    /// v.sort_unstable();
-    /// let a = OrderedCollection::from_sorted_iter(v);
-    /// assert_eq!(a.find_gte(50), Some(&89));
+    /// let x = OrderedCollection::from(vec![42, 89, 7, 12]);
+    /// assert_eq!(x[0], 7);
+    /// assert_eq!(x[3], 89);
     /// ```
+    #[inline]
+    fn index(&self, rank: usize) -> &T {
+        let n = self.items.len();
+        if rank >= n {
+            index_out_of_bounds(rank, n);
+        }
+        // in bounds because rank < n
+        self.at(sorted_to_eytzinger(rank, n))
+    }
+}
+
+/// Panic with the message that `Vec` uses for an out-of-bounds index.
+///
+/// This is kept out of line so that it doesn't get in the way of optimizing the caller.
+#[cold]
+#[inline(never)]
+fn index_out_of_bounds(index: usize, len: usize) -> ! {
+    panic!(
+        "index out of bounds: the len is {} but the index is {}",
+        len, index
+    )
+}
+
+/// A collection that is known to be sorted.
+///
+/// A `Sorted` can only be constructed by checking that its contents are sorted, or by sorting
+/// them. This makes it possible to construct an `OrderedCollection` from it without sorting the
+/// elements again, and without the risk of incorrect lookups that comes with `from_sorted_iter`.
+///
+/// # Examples
+///
+/// ```
+/// # use ordsearch::{OrderedCollection, Sorted};
+/// let v = Sorted::new(vec![7, 12, 42, 89]).unwrap();
+/// let a = OrderedCollection::from(v);
+/// assert_eq!(a.find_gte(50), Some(&89));
+///
+/// assert!(Sorted::new(vec![42, 89, 7, 12]).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sorted<I>(I);
+
+impl<T: Ord> Sorted<Vec<T>> {
+    /// Wrap `v` if it is sorted.
     ///
-    /// The `OrderedCollection` can also be over references to somewhere else:
+    /// Returns `v` back as the error if it is not sorted.
+    pub fn new(v: Vec<T>) -> Result<Self, Vec<T>> {
+        if v.windows(2).all(|w| w[0] <= w[1]) {
+            Ok(Sorted(v))
+        } else {
+            Err(v)
+        }
+    }
+
+    /// Sort `v` and wrap it.
+    pub fn sort(mut v: Vec<T>) -> Self {
+        v.sort_unstable();
+        Sorted(v)
+    }
+}
+
+impl<I> Sorted<I> {
+    /// Unwrap the sorted collection.
+    pub fn into_inner(self) -> I {
+        self.0
+    }
+}
+
+impl<I> AsRef<I> for Sorted<I> {
+    fn as_ref(&self) -> &I {
+        &self.0
+    }
+}
+
+/// Statistics about the construction of an `OrderedCollection`.
+///
+/// These are returned by `OrderedCollection::from_with_stats`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuildStats {
+    /// The number of elements in the collection.
+    pub len: usize,
+    /// Whether the input had to be sorted, because it was not already in sorted order. If it did
+    /// not, `from_sorted_iter` would have been enough.
+    pub needed_sort: bool,
+    /// The number of element writes performed while laying out the (sorted) collection. Writes
+    /// done by the sort are not included.
+    pub writes: usize,
+}
+
+/// Where a query landed relative to the elements of an `OrderedCollection`.
+///
+/// This is returned by `OrderedCollection::find_gte_detailed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchResult<T> {
+    /// An element is equal to the query. This is the first such element in sorted order.
+    Exact(T),
+    /// The query is between two elements. This is the larger of the two.
+    Between(T),
+    /// The query is smaller than every element. This is the smallest element.
+    BelowMin(T),
+    /// The query is larger than every element, or the collection is empty.
+    AboveMax,
+}
+
+/// The error returned when a lookup would take more steps than it was allowed.
+///
+/// This is returned by `OrderedCollection::find_gte_budgeted`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BudgetExceeded {
+    /// The number of levels of the search tree, which is the most steps a lookup may take.
+    pub depth: u32,
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "lookup may take up to {} steps, which exceeds its budget",
+            self.depth
+        )
+    }
+}
+
+impl Error for BudgetExceeded {}
+
+/// A description of how an `OrderedCollection` lays out its elements in memory.
+///
+/// This is returned by `OrderedCollection::layout_info`, and is meant for budgeting the memory
+/// used by many collections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutInfo {
+    /// The number of elements in the collection.
+    pub len: usize,
+    /// The number of slots of `size_of::<T>()` bytes that the layout occupies, including any
+    /// padding. This is always `OrderedCollection::capacity_for(len)`.
+    pub slots: usize,
+    /// The alignment, in bytes, of the start of the layout.
+    pub align: usize,
+}
+
+/// A builder for an `OrderedCollection` with non-default construction options.
+///
+/// A `Builder` is created with `OrderedCollection::builder`.
+///
+/// # Examples
+///
+/// ```
+/// # use ordsearch::OrderedCollection;
+/// let a = OrderedCollection::builder()
+///     .dedup(true)
+///     .build(vec![42, 89, 7, 42, 12, 7]);
+/// assert_eq!(a.len(), 4);
+/// assert_eq!(a.find_gte(50), Some(&89));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Builder<T> {
+    dedup: bool,
+    presorted: bool,
+    prefetch_offset: Option<usize>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Ord> Builder<T> {
+    /// Keep only one copy of each distinct element.
+    #[must_use = "builder methods return the updated builder"]
+    pub fn dedup(mut self, dedup: bool) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Assume that the elements are already sorted, and do not sort them.
     ///
-    /// ```
-    /// # use std::collections::BTreeSet;
-    /// # use ordsearch::OrderedCollection;
+    /// If the elements are *not* sorted, no error will be given, but lookups will give incorrect
+    /// results.
+    #[must_use = "builder methods return the updated builder"]
+    pub fn presorted(mut self, presorted: bool) -> Self {
+        self.presorted = presorted;
+        self
+    }
+
+    /// Prefetch `offset` elements past the start of the cache line of descendants during lookups,
+    /// instead of picking the offset based on the size of `T`.
     ///
-    /// let mut s = BTreeSet::new();
-    /// s.insert(42);
-    /// s.insert(89);
-    /// s.insert(7);
-    /// s.insert(12);
-    /// let a = OrderedCollection::from_sorted_iter(s.iter());
-    /// assert_eq!(a.find_gte(50), Some(&&89));
-    /// ```
+    /// This only affects the speed of lookups, not their results, and it only has an effect with
+    /// the (non-default) `nightly` feature.
+    #[must_use = "builder methods return the updated builder"]
+    pub fn prefetch_offset(mut self, offset: usize) -> Self {
+        self.prefetch_offset = Some(offset);
+        self
+    }
+
+    /// Construct the collection from the elements of `v`.
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn build(self, mut v: Vec<T>) -> OrderedCollection<T> {
+        if !self.presorted {
+            v.sort_unstable();
+        }
+        if self.dedup {
+            v.dedup();
+        }
+        let mut x = OrderedCollection::from_sorted_iter(v);
+        if let Some(offset) = self.prefetch_offset {
+            x.offset = offset;
+        }
+        x
+    }
+}
+
+/// A reusable allocation for repeatedly rebuilding an `OrderedCollection`.
+///
+/// When a collection is rebuilt periodically, handing the collection that is no longer needed
+/// back to the `Rebuilder` with `recycle` lets the next rebuild reuse its memory instead of
+/// allocating anew. Collections built by a `Rebuilder` keep the spare capacity of the allocation
+/// they were built in.
+///
+/// # Examples
+///
+/// ```
+/// # use ordsearch::Rebuilder;
+/// let mut r = Rebuilder::new();
+/// let mut x = r.rebuild_from_sorted(0..100);
+/// for i in 1..10 {
+///     let old = std::mem::replace(&mut x, r.rebuild_from_sorted(i..i + 100));
+///     r.recycle(old);
+/// }
+/// assert_eq!(x.find_gte(0), Some(&9));
+/// ```
+#[derive(Debug)]
+pub struct Rebuilder<T> {
+    buf: Vec<T>,
+}
+
+impl<T> Default for Rebuilder<T> {
+    fn default() -> Self {
+        Rebuilder { buf: Vec::new() }
+    }
+}
+
+impl<T: Ord> Rebuilder<T> {
+    /// Create a `Rebuilder` that does not hold an allocation yet.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Construct a new `OrderedCollection` from an iterator over sorted elements, reusing the
+    /// allocation held by the `Rebuilder` if it is large enough.
     ///
-    pub fn from_sorted_iter<I>(iter: I) -> Self
+    /// This is otherwise like `OrderedCollection::from_sorted_iter`. If the iterator is *not*
+    /// sorted, no error will be given, but lookups will give incorrect results.
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn rebuild_from_sorted<I>(&mut self, iter: I) -> OrderedCollection<T>
     where
         I: IntoIterator<Item = T>,
         I::IntoIter: ExactSizeIterator<Item = T>,
     {
-        let mut iter = iter.into_iter();
+        let iter = iter.into_iter();
         let n = iter.len();
-        let mut v = Vec::with_capacity(n);
-        eytzinger_walk(&mut v, &mut iter, 0);
-
-        // it's now safe to set the length, since all `n` elements have been inserted.
-        unsafe { v.set_len(n) };
-
-        #[cfg(feature = "nightly")]
-        {
-            let mut mask = 1;
-            while mask <= n {
-                mask <<= 1;
-            }
-            mask -= 1;
+        let mut v = mem::take(&mut self.buf);
+        v.reserve_exact(n);
+        OrderedCollection::from_layout_with_spare(eytzinger_fill(v, iter, n))
+    }
 
-            OrderedCollection {
-                items: v,
-                mask: mask,
-            }
+    /// Take back the allocation of a collection that is no longer needed, so that the next
+    /// rebuild can use it.
+    ///
+    /// The elements of the collection are dropped. If the `Rebuilder` already holds a larger
+    /// allocation, that one is kept instead.
+    pub fn recycle(&mut self, c: OrderedCollection<T>) {
+        let mut v = c.items;
+        v.clear();
+        if v.capacity() > self.buf.capacity() {
+            self.buf = v;
         }
-        #[cfg(not(feature = "nightly"))]
-        OrderedCollection { items: v }
     }
+}
 
-    /// Construct a new `OrderedCollection` from a slice of elements.
+/// A collection that is being filled one sorted element at a time.
+///
+/// This is returned by [`OrderedCollection::with_capacity`]. Each element passed to
+/// `push_sorted` is written straight to its final position in the search layout, and `finish`
+/// turns the filled layout into an `OrderedCollection` without moving the elements again.
+///
+/// # Examples
+///
+/// ```
+/// # use ordsearch::OrderedCollection;
+/// let mut b = OrderedCollection::with_capacity(3);
+/// b.push_sorted(7);
+/// b.push_sorted(12);
+/// b.push_sorted(42);
+/// let x = b.finish();
+/// assert_eq!(x.find_gte(8), Some(&12));
+/// ```
+pub struct SortedBuilder<T> {
+    v: Vec<T>,
+    n: usize,
+    written: usize,
+    next: usize,
+}
+
+impl<T: Ord> SortedBuilder<T> {
+    /// Add the next element.
     ///
-    /// Note that the underlying slice will be reordered!
+    /// Elements must be pushed in sorted order. If they are *not*, no error will be given, but
+    /// lookups on the finished collection will give incorrect results.
     ///
-    /// # Examples
+    /// # Panics
     ///
-    /// ```
-    /// # use ordsearch::OrderedCollection;
-    /// let mut vals = [42, 89, 7, 12];
-    /// let a = OrderedCollection::from_slice(&mut vals);
-    /// assert_eq!(a.find_gte(50), Some(&&89));
-    /// ```
-    pub fn from_slice<'a>(v: &'a mut [T]) -> OrderedCollection<&'a T> {
-        v.sort_unstable();
-        OrderedCollection::from_sorted_iter(v.into_iter().map(|x| &*x))
+    /// Panics if the builder already holds as many elements as its capacity.
+    pub fn push_sorted(&mut self, x: T) {
+        let n = self.n;
+        assert!(
+            self.written < n,
+            "pushed more elements than the builder has capacity for"
+        );
+        // safe because next < n while fewer than n elements have been written, and the slot has
+        // not been written to yet. as in eytzinger_walk, slots beyond the length of the Vec must
+        // be written through the raw pointer.
+        unsafe { ptr::write(self.v.as_mut_ptr().add(self.next), x) };
+        self.written += 1;
+        self.next = eytzinger_next(self.next, n);
     }
 
-    /// Find the smallest value `v` such that `v >= x`.
-    ///
-    /// Returns `None` if there is no such `v`.
+    /// Turn the filled builder into an `OrderedCollection`.
     ///
-    /// # Examples
+    /// # Panics
     ///
-    /// ```
-    /// # use ordsearch::OrderedCollection;
-    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
-    /// assert_eq!(x.find_gte(0), Some(&1));
-    /// assert_eq!(x.find_gte(1), Some(&1));
-    /// assert_eq!(x.find_gte(3), Some(&4));
-    /// assert_eq!(x.find_gte(6), Some(&8));
-    /// assert_eq!(x.find_gte(8), Some(&8));
-    /// assert_eq!(x.find_gte(64), Some(&64));
-    /// assert_eq!(x.find_gte(65), None);
-    /// ```
-    pub fn find_gte<'a, X>(&'a self, x: X) -> Option<&'a T>
-    where
-        T: Borrow<X>,
-        X: Ord,
-    {
-        use std::mem;
-
-        let mut i = 0;
-        let multiplier = 64 / mem::size_of::<T>();
-        let offset = multiplier + multiplier / 2;
-        let _ = offset; // avoid warning about unused w/o nightly
-
-        while i < self.items.len() {
-            #[cfg(feature = "nightly")]
-            {
-                use prefetch::prefetch::*;
-                // unsafe is safe because pointer is never dereferenced
-                unsafe {
-                    prefetch::<Read, High, Data, _>(
-                        self.items
-                            .as_ptr()
-                            .offset(((multiplier * i + offset) & self.mask) as isize),
-                    )
-                };
-            }
+    /// Panics if fewer elements have been pushed than the builder has capacity for.
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn finish(mut self) -> OrderedCollection<T> {
+        let n = self.n;
+        assert_eq!(
+            self.written, n,
+            "pushed fewer elements than the builder has capacity for"
+        );
+        let mut v = mem::take(&mut self.v);
+        // it's now safe to set the length, since all `n` elements have been written. the builder
+        // no longer owns them, so there's nothing for it to drop.
+        unsafe { v.set_len(n) };
+        self.written = 0;
+        OrderedCollection::from_layout(v)
+    }
+}
 
-            // safe because i < self.items.len()
-            i = if x.borrow() <= unsafe { self.items.get_unchecked(i) }.borrow() {
-                2 * i + 1
-            } else {
-                2 * i + 2
-            };
+impl<T> Drop for SortedBuilder<T> {
+    fn drop(&mut self) {
+        // drop the elements that have been written, which are the first `written` in sorted order
+        for r in 0..self.written {
+            // safe because the first `written` elements in sorted order have been initialized
+            let i = sorted_to_eytzinger(r, self.n);
+            unsafe { ptr::drop_in_place(self.v.as_mut_ptr().add(i)) };
         }
+    }
+}
 
-        // we want ffs(~(i + 1))
-        // since ctz(x) = ffs(x) - 1
-        // we use ctz(~(i + 1)) + 1
-        let j = (i + 1) >> ((!(i + 1)).trailing_zeros() + 1);
-        if j == 0 {
-            None
-        } else {
-            Some(unsafe { self.items.get_unchecked(j - 1) })
-        }
+impl<T: Ord> From<Sorted<Vec<T>>> for OrderedCollection<T> {
+    /// Construct a new `OrderedCollection` from a vector that is known to be sorted.
+    ///
+    /// Unlike `From<Vec<T>>`, this does not sort the elements.
+    fn from(v: Sorted<Vec<T>>) -> OrderedCollection<T> {
+        Self::from_sorted_iter(v.0)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::OrderedCollection;
+/// The destination of `eytzinger_walk`.
+///
+/// If the walk is interrupted by a panic, dropping the guard drops the elements that have been
+/// written so far. Since they are written in sorted order, these are the first `written` elements
+/// in sorted order.
+struct WalkGuard<'a, T: 'a> {
+    v: &'a mut Vec<T>,
+    n: usize,
+    written: usize,
+}
 
-    #[test]
-    fn complete_exact() {
-        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
-        assert_eq!(x.find_gte(1), Some(&1));
-        assert_eq!(x.find_gte(2), Some(&2));
-        assert_eq!(x.find_gte(4), Some(&4));
-        assert_eq!(x.find_gte(8), Some(&8));
-        assert_eq!(x.find_gte(16), Some(&16));
-        assert_eq!(x.find_gte(32), Some(&32));
-        assert_eq!(x.find_gte(64), Some(&64));
+impl<'a, T> Drop for WalkGuard<'a, T> {
+    fn drop(&mut self) {
+        for r in 0..self.written {
+            // safe because the first `written` elements in sorted order have been initialized
+            unsafe { ptr::drop_in_place(self.v.as_mut_ptr().add(sorted_to_eytzinger(r, self.n))) };
+        }
     }
+}
 
-    #[test]
-    fn complete_approximate() {
-        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
-        assert_eq!(x.find_gte(0), Some(&1));
-        assert_eq!(x.find_gte(3), Some(&4));
-        assert_eq!(x.find_gte(5), Some(&8));
+/// Insert items from the sorted iterator `iter` into `out.v` in complete binary tree order.
+///
+/// Requires `iter` to be a sorted iterator.
+/// Requires v's capacity to be at least `out.n`.
+/// The length of `v` will not be changed by this function.
+///
+/// Panics if `iter` yields fewer than `out.n` elements.
+fn eytzinger_walk<I, T>(out: &mut WalkGuard<T>, iter: &mut I, i: usize)
+where
+    I: Iterator<Item = T>,
+{
+    if i >= out.n {
+        return;
+    }
+
+    // visit left child
+    eytzinger_walk(out, iter, 2 * i + 1);
+
+    // put data at the root
+    // we know the write below is safe because the Vec's capacity is at least `n`. note that we
+    // must write through the raw pointer, since slot `i` is beyond the Vec's length and is not yet
+    // initialized.
+    let next = iter
+        .next()
+        .expect("iterator yielded fewer elements than it reported");
+    unsafe { ptr::write(out.v.as_mut_ptr().add(i), next) };
+    out.written += 1;
+
+    // visit right child
+    eytzinger_walk(out, iter, 2 * i + 2);
+}
+
+/// Collect the first `n` elements of the sorted iterator `iter` into a vector in Eytzinger order.
+///
+/// Panics if `iter` yields fewer than `n` elements.
+fn eytzinger_layout<I, T>(iter: I, n: usize) -> Vec<T>
+where
+    I: Iterator<Item = T>,
+{
+    eytzinger_fill(Vec::with_capacity(n), iter, n)
+}
+
+/// Collect the first `n` elements of the sorted iterator `iter` into `v` in Eytzinger order.
+///
+/// Requires `v` to be empty, with a capacity of at least `n`.
+/// Panics if `iter` yields fewer than `n` elements.
+fn eytzinger_fill<I, T>(v: Vec<T>, iter: I, n: usize) -> Vec<T>
+where
+    I: Iterator<Item = T>,
+{
+    eytzinger_fill_counting(v, iter, n).0
+}
+
+/// Collect the first `n` elements of the sorted iterator `iter` into `v` in Eytzinger order, as
+/// `eytzinger_fill` does, and also return the number of element writes that took.
+fn eytzinger_fill_counting<I, T>(mut v: Vec<T>, mut iter: I, n: usize) -> (Vec<T>, usize)
+where
+    I: Iterator<Item = T>,
+{
+    debug_assert!(v.is_empty() && v.capacity() >= n);
+    let writes = {
+        let mut out = WalkGuard {
+            v: &mut v,
+            n,
+            written: 0,
+        };
+        eytzinger_walk(&mut out, &mut iter, 0);
+        let writes = out.written;
+        // all `n` elements have been inserted, so there's nothing for the guard to clean up.
+        mem::forget(out);
+        writes
+    };
+
+    // it's now safe to set the length, since all `n` elements have been inserted.
+    unsafe { v.set_len(n) };
+
+    (v, writes)
+}
+
+/// Compute the position in sorted order of the element at index `i` of an Eytzinger array of
+/// length `n`.
+#[inline]
+fn eytzinger_to_sorted(i: usize, n: usize) -> usize {
+    debug_assert!(i < n);
+
+    // the number of levels in the tree, and the number of nodes on the bottom level
+    let h = usize::BITS - n.leading_zeros();
+    let m = n - ((1 << (h - 1)) - 1);
+
+    // compute the rank as if the bottom level was full
+    let k = i + 1;
+    let d = usize::BITS - 1 - k.leading_zeros();
+    let r = ((2 * (k - (1 << d)) + 1) << (h - 1 - d)) - 1;
+
+    // in a full tree, every other rank is a bottom-level node. the bottom level is filled from the
+    // left, so discount the missing bottom-level nodes that would have preceded this node.
+    let leaves = r.div_ceil(2);
+    if leaves > m {
+        r - (leaves - m)
+    } else {
+        r
+    }
+}
+
+/// Compute the index in an Eytzinger array of length `n` of the element at position `r` in sorted
+/// order.
+///
+/// This is the inverse of `eytzinger_to_sorted`.
+#[inline]
+fn sorted_to_eytzinger(r: usize, n: usize) -> usize {
+    debug_assert!(r < n);
+
+    // the number of levels in the tree, and the number of nodes on the bottom level
+    let h = usize::BITS - n.leading_zeros();
+    let m = n - ((1 << (h - 1)) - 1);
+
+    // compute the rank the element would have if the bottom level was full. the bottom level is
+    // filled from the left, and in a full tree every other rank is a bottom-level node, so past
+    // the last bottom-level node every remaining element skips a (missing) bottom-level rank.
+    let r = if r / 2 < m { r } else { r + (r - 2 * m) + 1 };
+
+    // the number of trailing ones of a rank in a full tree is the node's height above the bottom
+    let t = (r + 1).trailing_zeros();
+    let d = h - 1 - t;
+    (1 << d) + ((r + 1) >> (t + 1)) - 1
+}
+
+/// Reorder the sorted slice `v` into Eytzinger order, in place.
+fn eytzinger_permute<T>(v: &mut [T]) {
+    let n = v.len();
+    let mut done = vec![0u64; n.div_ceil(64)];
+    for start in 0..n {
+        if done[start / 64] & (1 << (start % 64)) != 0 {
+            continue;
+        }
+
+        // follow the cycle of the permutation that starts at `start`, by repeatedly swapping the
+        // element held at `start` into the slot where it belongs.
+        done[start / 64] |= 1 << (start % 64);
+        let mut r = start;
+        loop {
+            let i = sorted_to_eytzinger(r, n);
+            if i == start {
+                break;
+            }
+            v.swap(start, i);
+            done[i / 64] |= 1 << (i % 64);
+            r = i;
+        }
+    }
+}
+
+/// Reorder the Eytzinger-ordered slice `v` into sorted order, in place.
+///
+/// This is the inverse of `eytzinger_permute`.
+fn eytzinger_unpermute<T>(v: &mut [T]) {
+    let n = v.len();
+    let mut done = vec![0u64; n.div_ceil(64)];
+    for start in 0..n {
+        if done[start / 64] & (1 << (start % 64)) != 0 {
+            continue;
+        }
+
+        // follow the cycle of the permutation that starts at `start`, by repeatedly pulling the
+        // element that belongs in the current slot into it.
+        let mut r = start;
+        loop {
+            done[r / 64] |= 1 << (r % 64);
+            let i = sorted_to_eytzinger(r, n);
+            if i == start {
+                break;
+            }
+            v.swap(r, i);
+            r = i;
+        }
+    }
+}
+
+/// The size of a cache line, in bytes.
+const CACHE_LINE: usize = 64;
+
+/// The number of elements of type `T` that fit in a cache line.
+#[inline]
+fn prefetch_multiplier<T>() -> usize {
+    // zero-sized types would otherwise divide by zero
+    CACHE_LINE / mem::size_of::<T>().max(1)
+}
+
+/// Compute the smallest all-ones bit pattern that is at least `n`.
+///
+/// This is `(n + 1).next_power_of_two() - 1`, except that it cannot overflow for any `n`.
+#[inline]
+fn eytzinger_mask(n: usize) -> usize {
+    // shifting by the full width of the type (when n == 0) is an overflow
+    usize::MAX.checked_shr(n.leading_zeros()).unwrap_or(0)
+}
+
+/// Given the index `i` past the bottom of an Eytzinger array at which a descent ended, find the
+/// index of the last element at which the descent went left.
+///
+/// Returns `None` if the descent never went left.
+#[inline]
+fn eytzinger_last_left(i: usize) -> Option<usize> {
+    // we want ffs(~(i + 1))
+    // since ctz(x) = ffs(x) - 1
+    // we use ctz(~(i + 1)) + 1
+    // if the descent never went left in a tree with as many levels as usize has bits, the shift
+    // is by the full width of the type, which is an overflow
+    let j = (i + 1)
+        .checked_shr((!(i + 1)).trailing_zeros() + 1)
+        .unwrap_or(0);
+    if j == 0 {
+        None
+    } else {
+        Some(j - 1)
+    }
+}
+
+/// Find the index of the first element in sorted order in an Eytzinger array of length `n`.
+///
+/// Returns `n` if the array is empty.
+fn eytzinger_first(n: usize) -> usize {
+    let mut i = 0;
+    while 2 * i + 1 < n {
+        i = 2 * i + 1;
+    }
+    i
+}
+
+/// Find the index of the last element in sorted order in an Eytzinger array of length `n`.
+///
+/// Returns `0` if the array is empty.
+fn eytzinger_last(n: usize) -> usize {
+    let mut i = 0;
+    while 2 * i + 2 < n {
+        i = 2 * i + 2;
+    }
+    i
+}
+
+/// Find the index of the element that follows index `i` in sorted order in an Eytzinger array of
+/// length `n`.
+///
+/// Returns `n` if `i` holds the last element in sorted order.
+fn eytzinger_next(mut i: usize, n: usize) -> usize {
+    if 2 * i + 2 < n {
+        // the successor is the leftmost node in the right subtree
+        i = 2 * i + 2;
+        while 2 * i + 1 < n {
+            i = 2 * i + 1;
+        }
+        return i;
+    }
+
+    // the successor is the first ancestor whose left subtree we are in
+    while i != 0 {
+        let parent = (i - 1) / 2;
+        if i == 2 * parent + 1 {
+            return parent;
+        }
+        i = parent;
+    }
+    n
+}
+
+/// An iterator over the elements of an Eytzinger-arranged slice in sorted order.
+struct InOrder<'a, T: 'a> {
+    items: &'a [T],
+    next: usize,
+}
+
+impl<'a, T> InOrder<'a, T> {
+    fn new(items: &'a [T]) -> Self {
+        InOrder {
+            items,
+            next: eytzinger_first(items.len()),
+        }
+    }
+
+    /// Start the iteration at the element at index `i` of the Eytzinger-arranged slice.
+    fn starting_at(items: &'a [T], i: usize) -> Self {
+        InOrder { items, next: i }
+    }
+}
+
+impl<'a, T> Iterator for InOrder<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.items.get(self.next)?;
+        self.next = eytzinger_next(self.next, self.items.len());
+        Some(item)
+    }
+}
+
+/// A cursor over the elements of an `OrderedCollection`, in sorted order.
+///
+/// This is returned by [`OrderedCollection::cursor_gte`]. Iterating the cursor yields the element
+/// it currently points to, and then advances it to the next element in sorted order.
+pub struct Cursor<'a, T: 'a> {
+    inner: InOrder<'a, T>,
+}
+
+impl<'a, T> Cursor<'a, T> {
+    /// Returns the element the cursor currently points to, without advancing it.
+    ///
+    /// Returns `None` if the cursor has moved past the last element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// let mut c = x.cursor_gte(3);
+    /// assert_eq!(c.current(), Some(&4));
+    /// assert_eq!(c.current(), Some(&4));
+    /// c.next();
+    /// assert_eq!(c.current(), Some(&8));
+    /// c.next();
+    /// assert_eq!(c.current(), None);
+    /// ```
+    #[must_use]
+    pub fn current(&self) -> Option<&'a T> {
+        self.inner.items.get(self.inner.next)
+    }
+}
+
+impl<'a, T> Iterator for Cursor<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// An iterator over a contiguous run of the elements of an `OrderedCollection`, in sorted order.
+///
+/// This is returned by [`OrderedCollection::iter`], [`OrderedCollection::range`], and
+/// [`OrderedCollection::range_from`]. Unlike the iterators returned by most other methods, it is
+/// a named type, so it can be stored in structs and spelled out in function signatures.
+pub struct SortedIter<'a, T: 'a> {
+    inner: InOrder<'a, T>,
+    remaining: usize,
+}
+
+impl<'a, T> SortedIter<'a, T> {
+    /// Iterate over the elements with sorted ranks in `start..end`.
+    fn ranks(items: &'a [T], start: usize, end: usize) -> Self {
+        let n = items.len();
+        let first = if start < n {
+            sorted_to_eytzinger(start, n)
+        } else {
+            n
+        };
+        SortedIter {
+            inner: InOrder::starting_at(items, first),
+            remaining: end.saturating_sub(start),
+        }
+    }
+}
+
+impl<'a, T> Iterator for SortedIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for SortedIter<'a, T> {}
+
+impl<T: Ord> OrderedCollection<T> {
+    /// Construct a new `OrderedCollection` from an iterator over sorted elements.
+    ///
+    /// Note that if the iterator is *not* sorted, no error will be given, but lookups will give
+    /// incorrect results. The given iterator must also implement `ExactSizeIterator` so that we
+    /// know the size of the lookup array.
+    ///
+    /// The reported length is trusted. If the iterator yields more elements than it reports, the
+    /// extra elements are ignored. If it yields fewer, this function panics. In that case, any
+    /// elements that had already been taken from the iterator are dropped.
+    ///
+    /// # Examples
+    ///
+    /// Using an already-sorted iterator:
+    ///
+    /// ```
+    /// # use std::collections::BTreeSet;
+    /// # use ordsearch::OrderedCollection;
+    ///
+    /// let mut s = BTreeSet::new();
+    /// s.insert(42);
+    /// s.insert(89);
+    /// s.insert(7);
+    /// s.insert(12);
+    /// let a = OrderedCollection::from_sorted_iter(s);
+    /// assert_eq!(a.find_gte(50), Some(&89));
+    /// ```
+    ///
+    /// Sorting a collection and then iterating (in this case, you'd likely use `new` instead):
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut v = vec![42, 89, 7, 12];
+    /// v.sort_unstable();
+    /// let a = OrderedCollection::from_sorted_iter(v);
+    /// assert_eq!(a.find_gte(50), Some(&89));
+    /// ```
+    ///
+    /// The `OrderedCollection` can also be over references to somewhere else:
+    ///
+    /// ```
+    /// # use std::collections::BTreeSet;
+    /// # use ordsearch::OrderedCollection;
+    ///
+    /// let mut s = BTreeSet::new();
+    /// s.insert(42);
+    /// s.insert(89);
+    /// s.insert(7);
+    /// s.insert(12);
+    /// let a = OrderedCollection::from_sorted_iter(s.iter());
+    /// assert_eq!(a.find_gte(50), Some(&&89));
+    /// ```
+    ///
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn from_sorted_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        let n = iter.len();
+        Self::from_layout(eytzinger_layout(iter, n))
+    }
+
+    /// Returns `true` if `iter` yields its elements in non-decreasing order.
+    ///
+    /// This checks the precondition of `from_sorted_iter` and the other constructors that take
+    /// their input as already sorted, in a single pass over `iter`. Empty and single-element inputs
+    /// are always sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let v = vec![7, 12, 12, 42, 89];
+    /// assert!(OrderedCollection::is_sorted_ascending(&v));
+    /// let a = OrderedCollection::from_sorted_iter(v);
+    /// assert_eq!(a.find_gte(50), Some(&89));
+    ///
+    /// assert!(!OrderedCollection::is_sorted_ascending(vec![42, 7]));
+    /// ```
+    #[must_use]
+    pub fn is_sorted_ascending<I: IntoIterator<Item = T>>(iter: I) -> bool {
+        let mut iter = iter.into_iter();
+        let mut prev = match iter.next() {
+            Some(x) => x,
+            None => return true,
+        };
+        for x in iter {
+            if x < prev {
+                return false;
+            }
+            prev = x;
+        }
+        true
+    }
+
+    /// Construct a new `OrderedCollection` from an iterator over sorted elements, returning an
+    /// error instead of aborting if the memory for the collection cannot be allocated.
+    ///
+    /// This is otherwise like `from_sorted_iter`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::try_from_sorted_iter(vec![7, 12, 42, 89]).unwrap();
+    /// assert_eq!(a.find_gte(50), Some(&89));
+    /// ```
+    pub fn try_from_sorted_iter<I>(iter: I) -> Result<Self, TryReserveError>
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        let n = iter.len();
+        let mut v = Vec::new();
+        v.try_reserve_exact(n)?;
+        Ok(Self::from_layout(eytzinger_fill(v, iter, n)))
+    }
+
+    /// Construct a new `OrderedCollection` from a vector of elements that are already in the
+    /// order of the search layout, such as one returned by `to_eytzinger_vec`.
+    ///
+    /// This is the cheapest way to construct a collection, since the elements are neither sorted
+    /// nor moved. If the elements are *not* in Eytzinger order, no error will be given, but
+    /// lookups will give incorrect results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::from(vec![42, 89, 7, 12]);
+    /// let b = OrderedCollection::from_eytzinger_vec(a.to_eytzinger_vec());
+    /// assert_eq!(b.find_gte(50), Some(&89));
+    /// ```
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn from_eytzinger_vec(v: Vec<T>) -> Self {
+        Self::from_layout(v)
+    }
+
+    /// Construct a new `OrderedCollection` from the parts returned by `raw_parts`.
+    ///
+    /// This is the counterpart of `raw_parts` for formats that store the layout and its length
+    /// separately. Like `from_eytzinger_vec`, it neither sorts nor moves the elements, and it only
+    /// checks their order in debug builds.
+    ///
+    /// # Safety
+    ///
+    /// The elements of `items` must be in the Eytzinger order of a sorted sequence, as returned by
+    /// `raw_parts` or `to_eytzinger_vec`. Breaking this is not undefined behavior: lookups never
+    /// index past the end of `items` whatever its order, but they give incorrect results.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `items` does not hold exactly `n` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::from(vec![42, 89, 7, 12]);
+    /// let (items, n) = a.raw_parts();
+    /// // safe because the parts come straight from another collection
+    /// let b = unsafe { OrderedCollection::from_raw_parts(items.to_vec(), n) };
+    /// assert!(a == b);
+    /// ```
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub unsafe fn from_raw_parts(items: Vec<T>, n: usize) -> Self {
+        assert_eq!(items.len(), n);
+        let x = Self::from_layout(items);
+        debug_assert!(x.debug_validate());
+        x
+    }
+
+    /// Construct a new `OrderedCollection` from the first `len` elements of an iterator over
+    /// sorted elements.
+    ///
+    /// This is like `from_sorted_iter`, except that the number of elements is given separately,
+    /// so the iterator does not need to implement `ExactSizeIterator`. If the iterator yields more
+    /// than `len` elements, the extra elements are ignored. As with `from_sorted_iter`, if the
+    /// iterator is *not* sorted, no error will be given, but lookups will give incorrect results.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the iterator yields fewer than `len` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::from_sorted_iter_with_len((0..).filter(|x| x % 7 == 0), 15);
+    /// assert_eq!(a.len(), 15);
+    /// assert_eq!(a.find_gte(50), Some(&56));
+    /// assert_eq!(a.find_gte(99), None);
+    /// ```
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn from_sorted_iter_with_len<I>(iter: I, len: usize) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self::from_layout(eytzinger_layout(iter.into_iter(), len))
+    }
+
+    /// Construct a new `OrderedCollection` from the first `len` elements of an iterator over
+    /// sorted elements that may fail to be produced.
+    ///
+    /// This is like `from_sorted_iter_with_len`, except that the iterator yields `Result`s. The
+    /// first `Err` stops the construction and is returned, without pulling any further items from
+    /// the iterator. Any elements that had already been placed are dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the iterator yields fewer than `len` items without yielding an `Err`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let ok = "1 2 4 8".split(' ').map(|s| s.parse::<u32>());
+    /// let a = OrderedCollection::try_from_sorted_results(ok, 4).unwrap();
+    /// assert_eq!(a.find_gte(3), Some(&4));
+    ///
+    /// let bad = "1 2 x 8".split(' ').map(|s| s.parse::<u32>());
+    /// assert!(OrderedCollection::try_from_sorted_results(bad, 4).is_err());
+    /// ```
+    pub fn try_from_sorted_results<I, E>(iter: I, len: usize) -> Result<Self, E>
+    where
+        I: IntoIterator<Item = Result<T, E>>,
+    {
+        // if an element fails, dropping the builder drops the elements pushed before it
+        let mut b = Self::with_capacity(len);
+        for x in iter.into_iter().take(len) {
+            b.push_sorted(x?);
+        }
+        assert_eq!(
+            b.written, len,
+            "iterator yielded fewer elements than it reported"
+        );
+        Ok(b.finish())
+    }
+
+    /// Construct a new `OrderedCollection` from the first `len` elements of a stream of sorted
+    /// elements.
+    ///
+    /// The stream is driven to completion, or until it has yielded `len` elements, before the
+    /// elements are laid out. As with `from_sorted_iter`, if the stream is *not* sorted, no error
+    /// will be given, but lookups will give incorrect results.
+    ///
+    /// This method is only available with the (non-default) `async` feature.
+    ///
+    /// # Panics
+    ///
+    /// The returned future panics if the stream yields fewer than `len` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate futures;
+    /// # extern crate ordsearch;
+    /// # use ordsearch::OrderedCollection;
+    /// # fn main() {
+    /// let s = futures::stream::iter(vec![7, 12, 42, 89]);
+    /// let a = futures::executor::block_on(OrderedCollection::from_sorted_stream(s, 4));
+    /// assert_eq!(a.find_gte(50), Some(&89));
+    /// # }
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn from_sorted_stream<S>(stream: S, len: usize) -> impl Future<Output = Self>
+    where
+        S: Stream<Item = T>,
+    {
+        use futures_util::{FutureExt, StreamExt};
+        stream
+            .take(len)
+            .collect::<Vec<_>>()
+            .map(move |v| Self::from_sorted_iter_with_len(v, len))
+    }
+
+    /// Start building a collection of exactly `n` elements that are supplied one at a time, in
+    /// sorted order.
+    ///
+    /// This is useful when the number of elements is known up front, but the elements are
+    /// produced in a way that does not fit an iterator. See [`SortedBuilder`].
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn with_capacity(n: usize) -> SortedBuilder<T> {
+        SortedBuilder {
+            v: Vec::with_capacity(n),
+            n,
+            written: 0,
+            next: eytzinger_first(n),
+        }
+    }
+
+    /// Construct a new `OrderedCollection` from an iterator over sorted elements of unknown length.
+    ///
+    /// This is like `from_sorted_iter`, except that the iterator does not need to implement
+    /// `ExactSizeIterator`. To learn the number of elements, the iterator is first collected into
+    /// a `Vec`, which is an extra allocation. As with `from_sorted_iter`, if the iterator is *not*
+    /// sorted, no error will be given, but lookups will give incorrect results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::from_sorted_iter_unsized((0..100).filter(|x| x % 7 == 0));
+    /// assert_eq!(a.len(), 15);
+    /// assert_eq!(a.find_gte(50), Some(&56));
+    /// ```
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn from_sorted_iter_unsized<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self::from_sorted_iter(iter.into_iter().collect::<Vec<_>>())
+    }
+
+    /// Construct a new `OrderedCollection` from an iterator over sorted elements, using its size
+    /// hint as the number of elements if the hint is exact.
+    ///
+    /// If the lower and upper bounds of `size_hint` agree, the elements are laid out directly as
+    /// with `from_sorted_iter_with_len`. Otherwise, this falls back to
+    /// `from_sorted_iter_unsized`, which collects the elements first. As with `from_sorted_iter`,
+    /// if the iterator is *not* sorted, no error will be given, but lookups will give incorrect
+    /// results.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size hint is exact, but the iterator yields fewer elements than it says.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// // `Chain` does not implement `ExactSizeIterator`, but its size hint is exact here
+    /// let a = OrderedCollection::from_sorted_iter_hint((0..10).chain(20..30));
+    /// assert_eq!(a.find_gte(15), Some(&20));
+    /// ```
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn from_sorted_iter_hint<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        match iter.size_hint() {
+            (lower, Some(upper)) if lower == upper => Self::from_sorted_iter_with_len(iter, lower),
+            _ => Self::from_sorted_iter_unsized(iter),
+        }
+    }
+
+    /// Construct a new `OrderedCollection` from a vector of elements, and report what the
+    /// construction involved.
+    ///
+    /// This gives the same collection as `From<Vec<T>>`, but skips the sort if the elements are
+    /// already sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let (a, stats) = OrderedCollection::from_with_stats(vec![42, 89, 7, 12]);
+    /// assert_eq!(a.find_gte(50), Some(&89));
+    /// assert_eq!(stats.len, 4);
+    /// assert!(stats.needed_sort);
+    /// assert_eq!(stats.writes, 4);
+    /// ```
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn from_with_stats(mut v: Vec<T>) -> (Self, BuildStats) {
+        let needed_sort = !v.windows(2).all(|w| w[0] <= w[1]);
+        if needed_sort {
+            v.sort_unstable();
+        }
+        let n = v.len();
+        let (items, writes) = eytzinger_fill_counting(Vec::with_capacity(n), v.into_iter(), n);
+        let stats = BuildStats {
+            len: n,
+            needed_sort,
+            writes,
+        };
+        (Self::from_layout(items), stats)
+    }
+
+    /// Construct a new `OrderedCollection` from a vector of elements, keeping only one copy of
+    /// each distinct element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::from_dedup(vec![42, 89, 7, 42, 12, 7]);
+    /// assert_eq!(a.len(), 4);
+    /// assert_eq!(a.find_gte(8), Some(&12));
+    /// ```
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn from_dedup(mut v: Vec<T>) -> Self {
+        v.sort_unstable();
+        v.dedup();
+        Self::from_sorted_iter(v)
+    }
+
+    /// Construct a new `OrderedCollection` from an iterator over sorted elements, keeping only one
+    /// copy of each distinct element.
+    ///
+    /// This is like `from_dedup`, except that the elements are not sorted first, and duplicates
+    /// are skipped as they are read rather than being stored and removed afterwards. Since only
+    /// consecutive duplicates are skipped, the iterator must be sorted. If it is *not*, no error
+    /// will be given, but lookups will give incorrect results, and some duplicates may be kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::from_sorted_iter_dedup(vec![7, 7, 12, 42, 42, 42, 89]);
+    /// assert_eq!(a.len(), 4);
+    /// assert_eq!(a.find_gte(8), Some(&12));
+    /// ```
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn from_sorted_iter_dedup<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut v = Vec::new();
+        for e in iter {
+            if v.last() != Some(&e) {
+                v.push(e);
+            }
+        }
+        eytzinger_permute(&mut v);
+        Self::from_layout(v)
+    }
+
+    /// Construct a new `OrderedCollection` from a vector of elements, without allocating a second
+    /// vector for the search layout.
+    ///
+    /// `From<Vec<T>>` moves the sorted elements into a newly allocated vector, so the peak memory
+    /// use during construction is roughly twice the size of the data. This method instead
+    /// permutes the sorted elements into place, at the cost of a slightly slower construction and
+    /// a bitmap with one bit per element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::from_vec_in_place(vec![42, 89, 7, 12]);
+    /// assert_eq!(a.find_gte(50), Some(&89));
+    /// ```
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn from_vec_in_place(mut v: Vec<T>) -> Self {
+        v.sort_unstable();
+        eytzinger_permute(&mut v);
+        Self::from_layout(v)
+    }
+
+    /// Construct a new `OrderedCollection` from a slice of elements.
+    ///
+    /// Note that the underlying slice will be reordered!
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut vals = [42, 89, 7, 12];
+    /// let a = OrderedCollection::from_slice(&mut vals);
+    /// assert_eq!(a.find_gte(50), Some(&&89));
+    /// ```
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn from_slice(v: &mut [T]) -> OrderedCollection<&T> {
+        v.sort_unstable();
+        OrderedCollection::from_sorted_iter(v.iter())
+    }
+
+    /// Construct a new `OrderedCollection` from a sorted slice of elements, without modifying it.
+    ///
+    /// Note that if the slice is *not* sorted, no error will be given, but lookups will give
+    /// incorrect results. Use `from_slice` if the slice may not be sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let vals = [7, 12, 42, 89];
+    /// let a = OrderedCollection::from_sorted_slice(&vals);
+    /// assert_eq!(a.find_gte(50), Some(&&89));
+    /// ```
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn from_sorted_slice(v: &[T]) -> OrderedCollection<&T> {
+        OrderedCollection::from_sorted_iter(v.iter())
+    }
+
+    /// Construct a new `OrderedCollection` from a copy of a slice of elements.
+    ///
+    /// Unlike `from_slice`, the underlying slice is left untouched, and the resulting collection
+    /// owns its elements, so lookups yield `&T` rather than `&&T`. The cost is that every element
+    /// is cloned, and that the collection holds a second copy of the data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let vals = [42, 89, 7, 12];
+    /// let a = OrderedCollection::from_slice_owned(&vals);
+    /// assert_eq!(a.find_gte(50), Some(&89));
+    /// assert_eq!(vals, [42, 89, 7, 12]);
+    /// ```
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn from_slice_owned(v: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        Self::from(v.to_vec())
+    }
+
+    /// Create a `Builder` for a collection with non-default construction options.
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn builder() -> Builder<T> {
+        Builder {
+            dedup: false,
+            presorted: false,
+            prefetch_offset: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Retain only the elements for which `f` returns `true`.
+    ///
+    /// `f` is called once for every element, in sorted order. Since the search layout depends on
+    /// the number of elements, the collection is rebuilt from the remaining elements afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut x = OrderedCollection::from(vec![1, 2, 3, 4, 5, 6]);
+    /// x.retain(|&v| v % 2 == 0);
+    /// assert_eq!(x.len(), 3);
+    /// assert_eq!(x.find_gte(3), Some(&4));
+    /// ```
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut v = self.take_sorted();
+        v.retain(f);
+        self.relayout(v);
+    }
+
+    /// Add the elements of a sorted iterator to the collection.
+    ///
+    /// The existing elements and the new ones are merged in a single linear pass, and the
+    /// collection is then rebuilt, which takes `O(n + m)` time. If the iterator is *not* sorted,
+    /// no error will be given, but lookups will give incorrect results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut x = OrderedCollection::from(vec![1, 4, 16]);
+    /// x.extend_from_sorted(vec![2, 8, 32]);
+    /// assert_eq!(x.len(), 6);
+    /// assert_eq!(x.find_gte(5), Some(&8));
+    /// ```
+    pub fn extend_from_sorted<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let old = self.take_sorted();
+        let mut a = old.into_iter().peekable();
+        let mut b = iter.into_iter().peekable();
+        let mut v = Vec::with_capacity(a.len() + b.size_hint().0);
+        loop {
+            let next = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) => {
+                    if x <= y {
+                        a.next()
+                    } else {
+                        b.next()
+                    }
+                }
+                (Some(_), None) => a.next(),
+                (None, _) => b.next(),
+            };
+            match next {
+                Some(e) => v.push(e),
+                None => break,
+            }
+        }
+        self.relayout(v);
+    }
+
+    /// Move all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// The two collections are merged in a single linear pass, and `self` is then rebuilt, which
+    /// takes `O(n + m)` time. Unlike `BTreeSet::append`, elements that are in both collections
+    /// are kept twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut a = OrderedCollection::from(vec![1, 4, 16]);
+    /// let mut b = OrderedCollection::from(vec![2, 4, 8]);
+    /// a.append(&mut b);
+    /// assert_eq!(a.len(), 6);
+    /// assert_eq!(a.count(4), 2);
+    /// assert!(b.is_empty());
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        self.extend_from_sorted(other.take_sorted());
+    }
+
+    /// Split the collection in two at the given value.
+    ///
+    /// All elements `>= at` are moved into the returned collection, and all elements `< at` are
+    /// kept in `self`. Both collections are rebuilt.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut a = OrderedCollection::from(vec![1, 2, 3, 4, 5]);
+    /// let b = a.split_off(3);
+    /// assert_eq!(a.len(), 2);
+    /// assert_eq!(a.find_gte(3), None);
+    /// assert_eq!(b.len(), 3);
+    /// assert_eq!(b.find_gte(0), Some(&3));
+    /// ```
+    #[must_use = "use `retain` if the split off elements are not needed"]
+    pub fn split_off<X>(&mut self, at: X) -> Self
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let mut v = self.take_sorted();
+        let tail = v.split_off(v.partition_point(|e| e.borrow() < at.borrow()));
+        self.relayout(v);
+        self.relayout_like(tail)
+    }
+
+    /// Split the collection in two by position in sorted order.
+    ///
+    /// The `k` smallest elements end up in the first returned collection, and the rest in the
+    /// second. Both collections are rebuilt. If `k > len()`, it is treated as `len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![3, 1, 4, 2]);
+    /// let (a, b) = x.split_at_rank(1);
+    /// assert_eq!(a.find_gte(0), Some(&1));
+    /// assert_eq!(a.len(), 1);
+    /// assert_eq!(b.find_gte(0), Some(&2));
+    /// assert_eq!(b.len(), 3);
+    /// ```
+    #[must_use = "splitting a collection has no effect unless the halves are used"]
+    pub fn split_at_rank(mut self, k: usize) -> (Self, Self) {
+        let mut v = self.take_sorted();
+        let tail = v.split_off(k.min(v.len()));
+        let tail = self.relayout_like(tail);
+        self.relayout(v);
+        (self, tail)
+    }
+
+    /// Iterate over the elements that are present in both `self` and `other`, in sorted order.
+    ///
+    /// This is computed with a single linear merge of the two collections, which is much cheaper
+    /// than looking up every element of one collection in the other. If an element occurs
+    /// multiple times in both collections, it is yielded as many times as it occurs in the
+    /// collection where it occurs the least.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::from(vec![1, 2, 2, 4, 8]);
+    /// let b = OrderedCollection::from(vec![2, 2, 2, 3, 8]);
+    /// let both: Vec<_> = a.intersection(&b).collect();
+    /// assert_eq!(both, [&2, &2, &8]);
+    /// ```
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn intersection<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a {
+        let mut a = InOrder::new(&self.items).peekable();
+        let mut b = InOrder::new(&other.items).peekable();
+        iter::from_fn(move || loop {
+            match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) => match x.cmp(y) {
+                    Ordering::Less => {
+                        a.next();
+                    }
+                    Ordering::Greater => {
+                        b.next();
+                    }
+                    Ordering::Equal => {
+                        b.next();
+                        return a.next();
+                    }
+                },
+                _ => return None,
+            }
+        })
+    }
+
+    /// Iterate over the distinct elements that are present in `self`, `other`, or both, in sorted
+    /// order.
+    ///
+    /// This is computed with a single linear merge of the two collections. Like with `BTreeSet`,
+    /// every value is yielded only once, even if it occurs multiple times in either collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::from(vec![1, 2, 2, 4]);
+    /// let b = OrderedCollection::from(vec![2, 3, 4, 4]);
+    /// let either: Vec<_> = a.union(&b).collect();
+    /// assert_eq!(either, [&1, &2, &3, &4]);
+    /// ```
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn union<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a {
+        let mut a = InOrder::new(&self.items).peekable();
+        let mut b = InOrder::new(&other.items).peekable();
+        let mut last = None;
+        iter::from_fn(move || loop {
+            let next = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) => {
+                    if x <= y {
+                        a.next()
+                    } else {
+                        b.next()
+                    }
+                }
+                (Some(_), None) => a.next(),
+                (None, _) => b.next(),
+            }?;
+
+            if last != Some(next) {
+                last = Some(next);
+                return last;
+            }
+        })
+    }
+
+    /// Iterate over the elements of `self` that are not present in `other`, in sorted order.
+    ///
+    /// This is computed with a single linear merge of the two collections. Values that occur in
+    /// `other` are skipped entirely, while values that do not are yielded as many times as they
+    /// occur in `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::from(vec![1, 1, 2, 3, 4]);
+    /// let b = OrderedCollection::from(vec![2, 4, 5]);
+    /// let only_a: Vec<_> = a.difference(&b).collect();
+    /// assert_eq!(only_a, [&1, &1, &3]);
+    /// ```
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn difference<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a {
+        let mut a = InOrder::new(&self.items);
+        let mut b = InOrder::new(&other.items).peekable();
+        iter::from_fn(move || loop {
+            let x = a.next()?;
+            while let Some(&y) = b.peek() {
+                if y < x {
+                    b.next();
+                } else {
+                    break;
+                }
+            }
+
+            if b.peek() != Some(&x) {
+                return Some(x);
+            }
+        })
+    }
+}
+
+any_alloc!(impl<T: Ord> {
+    /// Find the smallest value `v` such that `v >= x`.
+    ///
+    /// Returns `None` if there is no such `v`. If several elements are equal to `x`, this is always
+    /// the first of them in sorted order, that is, the one at the lowest index.
+    ///
+    /// The elements are compared with `x` through `Borrow<X>`, so the order of `X` must agree with
+    /// the order of `T` that the collection was sorted by, as the documentation of `Borrow`
+    /// requires. If it does not, no error will be given, but lookups will give incorrect results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert_eq!(x.find_gte(0), Some(&1));
+    /// assert_eq!(x.find_gte(1), Some(&1));
+    /// assert_eq!(x.find_gte(3), Some(&4));
+    /// assert_eq!(x.find_gte(6), Some(&8));
+    /// assert_eq!(x.find_gte(8), Some(&8));
+    /// assert_eq!(x.find_gte(64), Some(&64));
+    /// assert_eq!(x.find_gte(65), None);
+    /// ```
+    ///
+    /// Smart pointers such as `Rc` and `Arc` borrow as the value they point to, so a collection of
+    /// them can be searched by value:
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// use std::sync::Arc;
+    /// let x = OrderedCollection::from(vec![Arc::new(4), Arc::new(1), Arc::new(8)]);
+    /// assert_eq!(x.find_gte(3).map(|v| **v), Some(4));
+    /// assert_eq!(x.find_gte_ref(&8).map(|v| **v), Some(8));
+    /// ```
+    ///
+    /// With the (non-default) `tracing` feature, every lookup emits a `TRACE` event that records
+    /// the number of levels the descent went through, and whether an element equal to `x` was
+    /// found.
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte<X>(&self, x: X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        #[cfg(feature = "tracing")]
+        {
+            let end = self.descend_to_end(0, |e| x.borrow() <= e.borrow());
+            let found = eytzinger_last_left(end);
+            // the descent visits one element on every level above the one it ends on
+            let levels = usize::BITS - (end + 1).leading_zeros() - 1;
+            // in bounds because eytzinger_last_left only returns indices on the search path
+            let exact = found.is_some_and(|j| self.at(j).borrow() == x.borrow());
+            tracing::trace!(levels, exact, "find_gte");
+            found.map(|j| self.at(j))
+        }
+        #[cfg(not(feature = "tracing"))]
+        // in bounds because search only returns valid indices
+        self.search(x.borrow(), 0).map(|j| self.at(j))
+    }
+});
+
+impl<T: Ord> OrderedCollection<T> {
+    /// Find the smallest value `v` such that `v >= x`, taking the query by reference.
+    ///
+    /// This is like `find_gte`, except that `x` is borrowed rather than moved, so querying with a
+    /// large or non-`Copy` key does not require giving up ownership of it. `X` may also be
+    /// unsized, so a collection of `String` can be queried with a `&str`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![String::from("b"), String::from("d")]);
+    /// let q = String::from("c");
+    /// assert_eq!(x.find_gte_ref(&q).map(String::as_str), Some("d"));
+    /// assert_eq!(x.find_gte_ref("a").map(String::as_str), Some("b"));
+    /// assert_eq!(q, "c");
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte_ref<X>(&self, x: &X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord + ?Sized,
+    {
+        // in bounds because search only returns valid indices
+        self.search(x, 0).map(|j| self.at(j))
+    }
+
+    /// Find the smallest value `v` such that `v >= x`, comparing `T` directly.
+    ///
+    /// This is like `find_gte`, except that the query must be a `T`, so the comparisons are made
+    /// without going through `Borrow`. For `Copy` types, `Borrow` is free anyway, so this is
+    /// mostly useful for keeping the generated code simple to read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1u32, 2, 4, 8]);
+    /// assert_eq!(x.find_gte_copy(3), Some(&4));
+    /// assert_eq!(x.find_gte_copy(9), None);
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte_copy(&self, x: T) -> Option<&T>
+    where
+        T: Copy,
+    {
+        // in bounds because descend only returns valid indices
+        self.descend(0, |&e| x <= e).map(|j| self.at(j))
+    }
+
+    /// Find the smallest value `v` such that `v >= x`, for every `x` in `xs`, in parallel.
+    ///
+    /// The queries are split into chunks that are searched on the Rayon thread pool, each using
+    /// the same descent as `find_gte`. The results are in the same order as `xs`.
+    ///
+    /// This method is only available with the (non-default) `rayon` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// assert_eq!(x.find_gte_par(&[3, 0, 9]), [Some(&4), Some(&1), None]);
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte_par<X>(&self, xs: &[X]) -> Vec<Option<&T>>
+    where
+        T: Borrow<X> + Sync,
+        X: Ord + Sync,
+    {
+        use rayon::prelude::*;
+
+        // enough lookups per task that the cost of splitting the work is amortized
+        const CHUNK: usize = 1024;
+        xs.par_iter()
+            .with_min_len(CHUNK)
+            .map(|x| self.find_gte_ref(x))
+            .collect()
+    }
+
+    /// Find the smallest value `v` such that `v >= x`, and count the comparisons the lookup made.
+    ///
+    /// The lookup compares `x` with one element on every level it descends through, so the count
+    /// is `depth()` if the descent reaches the bottom level of the tree, and `depth() - 1` if
+    /// the bottom level is incomplete and the descent ends above it. It does not depend on
+    /// whether, or where, `x` is found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert_eq!(x.find_gte_counting(5), (Some(&8), 3));
+    /// assert_eq!(x.find_gte_counting(65), (None, 3));
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte_counting<X>(&self, x: X) -> (Option<&T>, usize)
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let comparisons = Cell::new(0);
+        let found = self.descend(0, |e| {
+            comparisons.set(comparisons.get() + 1);
+            x.borrow() <= e.borrow()
+        });
+        // in bounds because descend only returns valid indices
+        (found.map(|j| self.at(j)), comparisons.get())
+    }
+
+    /// Find the smallest value `v` such that `v >= x`, unless that could take more than
+    /// `max_steps` steps.
+    ///
+    /// A lookup takes at most one step for every level of the search tree, so this returns an
+    /// error, without searching, if `depth()` is greater than `max_steps`. Otherwise it returns
+    /// the same result as `find_gte`. Since the depth only grows with the logarithm of the number
+    /// of elements, this is mostly a safety valve against unexpectedly large collections.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert_eq!(x.find_gte_budgeted(5, 3), Ok(Some(&8)));
+    /// assert!(x.find_gte_budgeted(5, 2).is_err());
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte_budgeted<X>(&self, x: X, max_steps: usize) -> Result<Option<&T>, BudgetExceeded>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let depth = self.depth();
+        if depth as usize > max_steps {
+            return Err(BudgetExceeded { depth });
+        }
+        Ok(self.find_gte(x))
+    }
+
+    /// Find the smallest value `v` such that `cmp(v, x)` is not `Ordering::Less`.
+    ///
+    /// This is like `find_gte`, except that the elements are compared with `x` using `cmp`
+    /// rather than through `Borrow`, so `x` can be of any type that the elements can be compared
+    /// with. `cmp` must be consistent with the order of the elements: it must return `Less` for
+    /// a prefix of the elements in sorted order, and something else for the rest. If it does not,
+    /// no error will be given, but the result may be incorrect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![(1, 'a'), (4, 'b'), (9, 'c')]);
+    /// assert_eq!(x.find_gte_cmp(&3, |e, k| e.0.cmp(k)), Some(&(4, 'b')));
+    /// assert_eq!(x.find_gte_cmp(&10, |e, k| e.0.cmp(k)), None);
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte_cmp<X, F>(&self, x: &X, cmp: F) -> Option<&T>
+    where
+        X: ?Sized,
+        F: Fn(&T, &X) -> Ordering,
+    {
+        // in bounds because descend only returns valid indices
+        self.descend(0, |e| cmp(e, x) != Ordering::Less)
+            .map(|j| self.at(j))
+    }
+
+    /// Iterate over all the elements that are equal to `x`.
+    ///
+    /// This finds the first element equal to `x`, and then walks forward in sorted order until
+    /// the elements stop being equal to `x`. If no element is equal to `x`, the iterator is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![2, 5, 2, 2]);
+    /// assert_eq!(x.find_all_eq(2).count(), 3);
+    /// assert_eq!(x.find_all_eq(5).count(), 1);
+    /// assert_eq!(x.find_all_eq(3).count(), 0);
+    /// ```
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn find_all_eq<'a, X>(&'a self, x: X) -> impl Iterator<Item = &'a T> + 'a
+    where
+        T: Borrow<X>,
+        X: Ord + 'a,
+    {
+        let start = self.search(&x, 0).unwrap_or(self.items.len());
+        InOrder::starting_at(&self.items, start).take_while(move |e| (*e).borrow() == &x)
+    }
+
+    /// Returns the number of elements that are equal to `x`.
+    ///
+    /// This takes two descents, one to find the first element `>= x`, and one to find the first
+    /// element `> x`, regardless of how many elements are equal to `x`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![2, 5, 2, 2]);
+    /// assert_eq!(x.count(2), 3);
+    /// assert_eq!(x.count(5), 1);
+    /// assert_eq!(x.count(3), 0);
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn count<X>(&self, x: X) -> usize
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let n = self.items.len();
+        let rank = |i: Option<usize>| i.map_or(n, |i| eytzinger_to_sorted(i, n));
+        rank(self.search_gt(&x)) - rank(self.search(&x, 0))
+    }
+
+    /// Returns `true` if any element `v` satisfies `low <= v < high`.
+    ///
+    /// This takes a single lookup, so it is much cheaper than counting the elements in the range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// assert!(x.contains_range(3, 5));
+    /// assert!(!x.contains_range(5, 8));
+    /// assert!(!x.contains_range(4, 4));
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn contains_range<X>(&self, low: X, high: X) -> bool
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        self.find_gte_ref(&low).is_some_and(|v| v.borrow() < &high)
+    }
+
+    /// Find the smallest value `v` such that `v > x`.
+    ///
+    /// Returns `None` if there is no such `v`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert_eq!(x.find_gt(0), Some(&1));
+    /// assert_eq!(x.find_gt(1), Some(&2));
+    /// assert_eq!(x.find_gt(3), Some(&4));
+    /// assert_eq!(x.find_gt(63), Some(&64));
+    /// assert_eq!(x.find_gt(64), None);
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gt<X>(&self, x: X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        // in bounds because search_gt only returns valid indices
+        self.search_gt(x.borrow()).map(|j| self.at(j))
+    }
+
+    /// Find the element closest to `x` that is at most `eps` away from it.
+    ///
+    /// Returns `None` if no element is within `eps` of `x`. If two elements are equally close to
+    /// `x`, the smaller one is returned. The distances are computed by subtracting the smaller
+    /// of `x` and an element from the larger, so the subtraction must not overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![10, 20, 30]);
+    /// assert_eq!(x.find_within(18, 5), Some(&20));
+    /// assert_eq!(x.find_within(25, 5), Some(&20));
+    /// assert_eq!(x.find_within(14, 3), None);
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_within<X>(&self, x: X, eps: X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord + Copy + Sub<Output = X>,
+    {
+        let n = self.items.len();
+        let above = self.search(&x, 0);
+        let rank = above.map_or(n, |i| eytzinger_to_sorted(i, n));
+
+        // in bounds because search only returns valid indices, and rank - 1 is a valid rank
+        let above = above.map(|i| self.at(i));
+        let below = rank
+            .checked_sub(1)
+            .map(|r| self.at(sorted_to_eytzinger(r, n)));
+
+        let nearest = match (below, above) {
+            (Some(b), Some(a)) => {
+                if x - *b.borrow() <= *a.borrow() - x {
+                    (b, x - *b.borrow())
+                } else {
+                    (a, *a.borrow() - x)
+                }
+            }
+            (Some(b), None) => (b, x - *b.borrow()),
+            (None, Some(a)) => (a, *a.borrow() - x),
+            (None, None) => return None,
+        };
+        if nearest.1 <= eps {
+            Some(nearest.0)
+        } else {
+            None
+        }
+    }
+
+    /// Find the first element that is not less than `x`.
+    ///
+    /// This is the same as `find_gte`, and is named after C++'s `std::lower_bound`. Where
+    /// `std::lower_bound` returns the end iterator, this method returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// // std::vector<int> v = {1, 2, 2, 4};
+    /// // *std::lower_bound(v.begin(), v.end(), 2) == 2
+    /// // std::lower_bound(v.begin(), v.end(), 5) == v.end()
+    /// let x = OrderedCollection::from(vec![1, 2, 2, 4]);
+    /// assert_eq!(x.lower_bound(2), Some(&2));
+    /// assert_eq!(x.lower_bound(3), Some(&4));
+    /// assert_eq!(x.lower_bound(5), None);
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn lower_bound<X>(&self, x: X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        self.find_gte(x)
+    }
+
+    /// Find the first element that is greater than `x`.
+    ///
+    /// This is the same as `find_gt`, and is named after C++'s `std::upper_bound`. Where
+    /// `std::upper_bound` returns the end iterator, this method returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// // std::vector<int> v = {1, 2, 2, 4};
+    /// // *std::upper_bound(v.begin(), v.end(), 2) == 4
+    /// // std::upper_bound(v.begin(), v.end(), 4) == v.end()
+    /// let x = OrderedCollection::from(vec![1, 2, 2, 4]);
+    /// assert_eq!(x.upper_bound(1), Some(&2));
+    /// assert_eq!(x.upper_bound(2), Some(&4));
+    /// assert_eq!(x.upper_bound(4), None);
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn upper_bound<X>(&self, x: X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        self.find_gt(x)
+    }
+
+    /// Returns a cursor that starts at the smallest value `v` such that `v >= x`.
+    ///
+    /// The cursor can then be advanced through the remaining elements in sorted order. If there
+    /// is no such `v`, the cursor starts past the last element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// let c = x.cursor_gte(5);
+    /// assert_eq!(c.current(), Some(&8));
+    /// let rest: Vec<_> = c.collect();
+    /// assert_eq!(rest, [&8, &16, &32, &64]);
+    /// ```
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn cursor_gte<X>(&self, x: X) -> Cursor<'_, T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let start = self.search(x.borrow(), 0).unwrap_or(self.items.len());
+        Cursor {
+            inner: InOrder::starting_at(&self.items, start),
+        }
+    }
+
+    /// Iterate over the elements that fall within `range`, in sorted order.
+    ///
+    /// If the start of the range is past its end, the iterator is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert_eq!(x.range(2..16).collect::<Vec<_>>(), [&2, &4, &8]);
+    /// assert_eq!(x.range(3..=16).collect::<Vec<_>>(), [&4, &8, &16]);
+    /// assert_eq!(x.range(..3).len(), 2);
+    /// assert_eq!(x.range(65..).len(), 0);
+    /// ```
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn range<X, R>(&self, range: R) -> SortedIter<'_, T>
+    where
+        T: Borrow<X>,
+        X: Ord + ?Sized,
+        R: RangeBounds<X>,
+    {
+        let n = self.items.len();
+        let rank = |i: Option<usize>| i.map_or(n, |i| eytzinger_to_sorted(i, n));
+        let start = match range.start_bound() {
+            Bound::Included(x) => rank(self.search(x, 0)),
+            Bound::Excluded(x) => rank(self.search_gt(x)),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(x) => rank(self.search_gt(x)),
+            Bound::Excluded(x) => rank(self.search(x, 0)),
+            Bound::Unbounded => n,
+        };
+        SortedIter::ranks(&self.items, start, end)
+    }
+
+    /// Iterate over the elements `v` such that `v >= x`, in sorted order.
+    ///
+    /// This is equivalent to `range(x..)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// assert_eq!(x.range_from(3).collect::<Vec<_>>(), [&4, &8]);
+    /// ```
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn range_from<X>(&self, x: X) -> SortedIter<'_, T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        self.range(x..)
+    }
+
+    /// Find the smallest value `v` such that `v >= x`, resuming from where an earlier lookup
+    /// ended.
+    ///
+    /// Returns the result of the lookup along with a hint that can be passed to the next call.
+    /// When a stream of queries is non-decreasing, feeding each returned hint into the next call
+    /// lets the descent start close to the previous result rather than from the root, which
+    /// touches fewer cache lines. The first call in a stream should use a hint of `0`.
+    ///
+    /// The hint must be `0`, or one returned by a call with a query no greater than `x`. If it is
+    /// not, no error will be given, but the result may be incorrect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// let (hint, v) = x.find_gte_from_hint(3, 0);
+    /// assert_eq!(v, Some(&4));
+    /// let (hint, v) = x.find_gte_from_hint(5, hint);
+    /// assert_eq!(v, Some(&8));
+    /// let (_, v) = x.find_gte_from_hint(65, hint);
+    /// assert_eq!(v, None);
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte_from_hint<X>(&self, x: X, hint: usize) -> (usize, Option<&T>)
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let x = x.borrow();
+        match self.search(x, self.resume_root(x, hint)) {
+            // in bounds because search only returns valid indices
+            Some(j) => (j, Some(self.at(j))),
+            None => (self.items.len(), None),
+        }
+    }
+
+    /// Find the smallest value `v` such that `v >= x`, among the elements whose position in
+    /// sorted order is in `lo..hi`.
+    ///
+    /// Returns `None` if there is no such `v`. A `hi` beyond the length of the collection is
+    /// treated as the length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert_eq!(x.find_gte_in_rank_range(3, 0, 7), Some(&4));
+    /// assert_eq!(x.find_gte_in_rank_range(3, 4, 7), Some(&16));
+    /// assert_eq!(x.find_gte_in_rank_range(3, 0, 2), None);
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte_in_rank_range<X>(&self, x: X, lo: usize, hi: usize) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let n = self.items.len();
+        let hi = hi.min(n);
+        if lo >= hi {
+            return None;
+        }
+
+        // the result is the first element at or after both the unrestricted result and `lo`
+        let r = eytzinger_to_sorted(self.search(x.borrow(), 0)?, n).max(lo);
+        if r < hi {
+            // in bounds because `r < n`
+            Some(self.at(sorted_to_eytzinger(r, n)))
+        } else {
+            None
+        }
+    }
+
+    /// Find the smallest value `v` such that `v >= x`, and report where `x` landed relative to the
+    /// elements of the collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::{OrderedCollection, SearchResult};
+    /// let x = OrderedCollection::from(vec![2, 4, 8]);
+    /// assert_eq!(x.find_gte_detailed(4), SearchResult::Exact(&4));
+    /// assert_eq!(x.find_gte_detailed(5), SearchResult::Between(&8));
+    /// assert_eq!(x.find_gte_detailed(1), SearchResult::BelowMin(&2));
+    /// assert_eq!(x.find_gte_detailed(9), SearchResult::AboveMax);
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte_detailed<X>(&self, x: X) -> SearchResult<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let n = self.items.len();
+        match self.search(x.borrow(), 0) {
+            Some(j) => {
+                // in bounds because search only returns valid indices
+                let e = self.at(j);
+                if e.borrow() == x.borrow() {
+                    SearchResult::Exact(e)
+                } else if eytzinger_to_sorted(j, n) == 0 {
+                    SearchResult::BelowMin(e)
+                } else {
+                    SearchResult::Between(e)
+                }
+            }
+            None => SearchResult::AboveMax,
+        }
+    }
+
+    /// Find the position in sorted order of the smallest value `v` such that `v >= x`.
+    ///
+    /// Returns `None` if there is no such `v`. This is useful when payloads are kept in a separate
+    /// vector in sorted order, since the result indexes straight into it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let keys = vec![1, 2, 4, 8];
+    /// let payloads = ["one", "two", "four", "eight"];
+    /// let x = OrderedCollection::from(keys);
+    /// assert_eq!(x.find_gte_sorted_index(3).map(|i| payloads[i]), Some("four"));
+    /// assert_eq!(x.find_gte_sorted_index(9), None);
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte_sorted_index<X>(&self, x: X) -> Option<usize>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        self.search(x.borrow(), 0)
+            .map(|j| eytzinger_to_sorted(j, self.items.len()))
+    }
+
+    /// Find an element equal to `x`, or the position in sorted order at which `x` would have to be
+    /// inserted to keep the elements sorted.
+    ///
+    /// Like `slice::binary_search`, this returns `Ok` with the element if one is equal to `x`,
+    /// and `Err` with the insertion position otherwise. If several elements are equal to `x`,
+    /// the first of them in sorted order is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert_eq!(x.find_gte_or_pos(4), Ok(&4));
+    /// assert_eq!(x.find_gte_or_pos(5), Err(3));
+    /// assert_eq!(x.find_gte_or_pos(0), Err(0));
+    /// assert_eq!(x.find_gte_or_pos(65), Err(7));
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte_or_pos<X>(&self, x: X) -> Result<&T, usize>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let n = self.items.len();
+        match self.search(x.borrow(), 0) {
+            Some(j) => {
+                // in bounds because search only returns valid indices
+                let e = self.at(j);
+                if e.borrow() == x.borrow() {
+                    Ok(e)
+                } else {
+                    Err(eytzinger_to_sorted(j, n))
+                }
+            }
+            None => Err(n),
+        }
+    }
+
+    /// Find the smallest value `v` such that `v >= x`, starting the descent from a guess based on
+    /// interpolating between the smallest and the largest element.
+    ///
+    /// The guess is the position `x` would have if the elements were spread evenly between the
+    /// smallest and the largest one. The descent starts at the root of a subtree around that
+    /// position, after checking that the answer lies within the subtree. This skips the upper
+    /// half of the tree, and the two elements that bound the subtree are loaded independently of
+    /// each other rather than one after the other as in `find_gte`. This mainly helps for
+    /// large collections with near-uniformly distributed elements. If the guess is off, the
+    /// lookup falls back to a full descent from the root, so the result is always correct, but
+    /// loading the two bounding elements makes the lookup somewhat slower than `find_gte`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from((0..1000u32).map(|i| 3 * i).collect::<Vec<_>>());
+    /// assert_eq!(x.find_gte_interpolating(1000), Some(&1002));
+    /// assert_eq!(x.find_gte_interpolating(3000), None);
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte_interpolating(&self, x: T) -> Option<&T>
+    where
+        T: Copy + Into<u64>,
+    {
+        let n = self.items.len();
+        if n == 0 {
+            return None;
+        }
+        let (first, last) = (sorted_to_eytzinger(0, n), sorted_to_eytzinger(n - 1, n));
+        let (min, max) = (*self.at(first), *self.at(last));
+        if x <= min {
+            return Some(self.at(first));
+        }
+        if x > max {
+            return None;
+        }
+
+        // guess the position of `x`. `min < x <= max`, so the range can't be empty.
+        let (lo, hi, key) = (min.into(), max.into(), x.into());
+        let r = ((key - lo) as u128 * (n - 1) as u128 / (hi - lo) as u128) as usize;
+
+        // start from the ancestor of the guessed element that roots a subtree of about half the
+        // height of the tree, or from the guessed element itself if it is higher up
+        let h = usize::BITS - n.leading_zeros();
+        let d = h.saturating_sub(h.div_ceil(2) + 1);
+        let k = sorted_to_eytzinger(r, n) + 1;
+        let depth = usize::BITS - 1 - k.leading_zeros();
+        let root = (k >> depth.saturating_sub(d)) - 1;
+
+        // the answer lies in the subtree, or is the closest ancestor whose left subtree holds it,
+        // if the closest ancestor whose right subtree holds it is smaller than `x`, and the
+        // closest ancestor whose left subtree holds it is at least `x`.
+        let k = root + 1;
+        let below = (k >> (k.trailing_zeros() + 1)).checked_sub(1);
+        let above = eytzinger_last_left(root);
+        let fits =
+            below.map_or(true, |j| *self.at(j) < x) && above.map_or(true, |j| x <= *self.at(j));
+        let start = if fits { root } else { 0 };
+
+        // in bounds because search only returns valid indices
+        self.search(&x, start).map(|j| self.at(j))
+    }
+
+    /// Pick the prefetch distance that gives the fastest lookups on the current machine.
+    ///
+    /// Lookups prefetch elements a few levels further down the tree, at a fixed offset into the
+    /// cache line of descendants. The best offset depends on the cache latencies of the CPU. This
+    /// method times a batch of lookups for each of a few candidate offsets, and keeps the fastest
+    /// one for all subsequent lookups. The chosen offset is returned.
+    ///
+    /// This is a one-time cost that should be paid after construction, and it only affects the
+    /// speed of lookups, not their results. The measurements are only meaningful for large
+    /// collections, since small ones fit entirely in cache. Elements larger than a cache line
+    /// are always prefetched from the next element visited, so for those the offset is unused.
+    ///
+    /// This method is only available with the (non-default) `nightly` feature.
+    #[cfg(feature = "nightly")]
+    pub fn calibrate_prefetch(&mut self) -> usize {
+        use std::time::{Duration, Instant};
+
+        const QUERIES: usize = 10_000;
+        let n = self.items.len();
+        if n == 0 {
+            return self.offset;
+        }
+
+        let multiplier = prefetch_multiplier::<T>();
+        let mut candidates = vec![
+            0,
+            multiplier / 2,
+            multiplier,
+            multiplier + multiplier / 2,
+            2 * multiplier,
+        ];
+        candidates.dedup();
+
+        let mut best = (Duration::MAX, self.offset);
+        for &offset in &candidates {
+            self.offset = offset;
+
+            // query with elements of the collection, picked using the same LCG as the benchmarks
+            let mut r = 0usize;
+            let start = Instant::now();
+            for _ in 0..QUERIES {
+                r = r.wrapping_mul(1664525).wrapping_add(1013904223);
+                // in bounds because n is the number of elements
+                test::black_box(self.search(self.at(r % n), 0));
+            }
+            let took = start.elapsed();
+
+            if took < best.0 {
+                best = (took, offset);
+            }
+        }
+
+        self.offset = best.1;
+        self.offset
+    }
+
+    /// Returns `true` if every element of `xs` is present in the collection.
+    ///
+    /// `xs` must be sorted. This lets each lookup resume its descent close to where the previous
+    /// one ended, rather than from the root, which is cheaper than looking up every element
+    /// independently. If `xs` is *not* sorted, no error will be given, but the result may be
+    /// incorrect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert!(x.contains_all(&[2, 8, 64]));
+    /// assert!(!x.contains_all(&[2, 3, 64]));
+    /// assert!(x.contains_all::<i32>(&[]));
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn contains_all<X>(&self, xs: &[X]) -> bool
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let mut hint = 0;
+        for x in xs {
+            // in bounds because search only returns valid indices
+            match self.search(x, self.resume_root(x, hint)) {
+                Some(j) if self.at(j).borrow() == x => hint = j,
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// Find the root of the smallest subtree that must contain the answer to a lookup for `x`,
+    /// given that `hint` is the index of the answer to an earlier lookup for a value `<= x`.
+    ///
+    /// If `hint` is out of bounds, the whole tree is searched.
+    fn resume_root<X>(&self, x: &X, mut hint: usize) -> usize
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        if hint >= self.items.len() {
+            return 0;
+        }
+
+        // every element that precedes `hint` in sorted order is smaller than `x`, so the answer
+        // lies in the first subtree rooted at `hint` or one of its ancestors that is bounded from
+        // above by an element `>= x`.
+        while hint != 0 {
+            let parent = (hint - 1) / 2;
+            // in bounds because parent < hint < self.items.len()
+            if hint == 2 * parent + 1 && x <= self.at(parent).borrow() {
+                break;
+            }
+            hint = parent;
+        }
+        hint
+    }
+}
+
+any_alloc!(impl<T: Ord> {
+    /// Find the index of the smallest value `v` such that `v >= x`, descending from index `i`.
+    ///
+    /// The answer must lie either in the subtree rooted at `i`, or be the closest ancestor of `i`
+    /// whose left subtree contains `i`.
+    fn search<X>(&self, x: &X, i: usize) -> Option<usize>
+    where
+        T: Borrow<X>,
+        X: Ord + ?Sized,
+    {
+        self.descend(i, |e| x <= e.borrow())
+    }
+
+    /// Find the index of the smallest value `v` such that `v > x`.
+    fn search_gt<X>(&self, x: &X) -> Option<usize>
+    where
+        T: Borrow<X>,
+        X: Ord + ?Sized,
+    {
+        self.descend(0, |e| x < e.borrow())
+    }
+});
+
+impl<T> OrderedCollection<T> {
+    /// Wrap a vector whose elements are already in Eytzinger order.
+    fn from_layout(mut items: Vec<T>) -> Self {
+        // the collection never grows, so any spare capacity would be wasted
+        items.shrink_to_fit();
+        Self::from_layout_with_spare(items)
+    }
+
+    /// Wrap a vector whose elements are already in Eytzinger order, keeping any spare capacity
+    /// so that the allocation can be reused.
+    fn from_layout_with_spare(items: Vec<T>) -> Self {
+        let mask = eytzinger_mask(items.len());
+        let multiplier = prefetch_multiplier::<T>();
+        OrderedCollection {
+            items,
+            mask,
+            offset: multiplier + multiplier / 2,
+            prefetch: true,
+        }
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T: Ord, A: Allocator> OrderedCollection<T, A> {
+    /// Construct a new `OrderedCollection` from a vector of elements allocated with the allocator
+    /// `A`.
+    ///
+    /// The elements are sorted and then permuted into the search layout in place, so the
+    /// collection keeps using the allocation of `v`, and allocates nothing else from `A`. This
+    /// method is only available with the (non-default, nightly-only) `allocator_api` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    /// # use ordsearch::OrderedCollection;
+    /// use std::alloc::Global;
+    /// let mut v = Vec::new_in(Global);
+    /// v.extend([42, 89, 7, 12]);
+    /// let a = OrderedCollection::from_vec_in(v);
+    /// assert_eq!(a.find_gte(50), Some(&89));
+    /// ```
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn from_vec_in(mut v: Vec<T, A>) -> Self {
+        v.sort_unstable();
+        eytzinger_permute(&mut v);
+        Self::from_layout_in(v)
+    }
+
+    /// Construct a new `OrderedCollection` from an iterator over sorted elements, storing them
+    /// with the allocator `alloc`.
+    ///
+    /// This is like `from_sorted_iter`. If the iterator is *not* sorted, no error will be given,
+    /// but lookups will give incorrect results. This method is only available with the
+    /// (non-default, nightly-only) `allocator_api` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    /// # use ordsearch::OrderedCollection;
+    /// use std::alloc::Global;
+    /// let a = OrderedCollection::from_sorted_iter_in(vec![7, 12, 42, 89], Global);
+    /// assert_eq!(a.find_gte(50), Some(&89));
+    /// ```
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn from_sorted_iter_in<I>(iter: I, alloc: A) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        let mut v = Vec::with_capacity_in(iter.size_hint().0, alloc);
+        v.extend(iter);
+        eytzinger_permute(&mut v);
+        Self::from_layout_in(v)
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T, A: Allocator> OrderedCollection<T, A> {
+    /// Wrap a vector whose elements are already in Eytzinger order, as `from_layout` does, for a
+    /// vector with any allocator.
+    fn from_layout_in(mut items: Vec<T, A>) -> Self {
+        items.shrink_to_fit();
+        let mask = eytzinger_mask(items.len());
+        let multiplier = prefetch_multiplier::<T>();
+        OrderedCollection {
+            items,
+            mask,
+            offset: multiplier + multiplier / 2,
+            prefetch: true,
+        }
+    }
+
+    /// Returns a reference to the allocator that holds the elements of the collection.
+    ///
+    /// This method is only available with the (non-default, nightly-only) `allocator_api` feature.
+    #[must_use]
+    pub fn allocator(&self) -> &A {
+        self.items.allocator()
+    }
+}
+
+any_alloc!(impl<T> {
+    /// Get the element at index `i` of the search layout.
+    ///
+    /// `i` must be in bounds. This is only checked if the `safe` feature is enabled.
+    #[inline(always)]
+    fn at(&self, i: usize) -> &T {
+        debug_assert!(i < self.items.len());
+        #[cfg(feature = "safe")]
+        {
+            &self.items[i]
+        }
+        #[cfg(not(feature = "safe"))]
+        // safe because all callers pass indices that are in bounds
+        unsafe {
+            self.items.get_unchecked(i)
+        }
+    }
+
+    /// Get a mutable reference to the element at index `i` of the search layout.
+    ///
+    /// `i` must be in bounds. This is only checked if the `safe` feature is enabled. Callers must
+    /// not change the element in a way that changes its order.
+    #[inline(always)]
+    fn at_mut(&mut self, i: usize) -> &mut T {
+        debug_assert!(i < self.items.len());
+        #[cfg(feature = "safe")]
+        {
+            &mut self.items[i]
+        }
+        #[cfg(not(feature = "safe"))]
+        // safe because all callers pass indices that are in bounds
+        unsafe {
+            self.items.get_unchecked_mut(i)
+        }
+    }
+
+    /// Descend from index `i`, going left at every element for which `go_left` returns `true`,
+    /// and return the index of the last element at which the descent went left.
+    ///
+    /// If `go_left` is `false` for a prefix of the elements in sorted order and `true` for the
+    /// rest, this is the index of the first element in sorted order for which it is `true`.
+    fn descend<F>(&self, i: usize, go_left: F) -> Option<usize>
+    where
+        F: Fn(&T) -> bool,
+    {
+        eytzinger_last_left(self.descend_to_end(i, go_left))
+    }
+
+    /// Descend from index `i` as `descend` does, and return the index past the bottom of the tree
+    /// at which the descent ended.
+    #[inline(always)]
+    fn descend_to_end<F>(&self, mut i: usize, go_left: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        #[cfg(feature = "nightly")]
+        let (multiplier, offset) = (prefetch_multiplier::<T>(), self.offset);
+
+        while i < self.items.len() {
+            #[cfg(feature = "nightly")]
+            self.prefetch_below(i, multiplier, offset);
+
+            // in bounds because i < self.items.len()
+            i = if go_left(self.at(i)) {
+                2 * i + 1
+            } else {
+                2 * i + 2
+            };
+        }
+
+        i
+    }
+
+    /// Descend from index `i` exactly as `descend` does, but keep track of the last element at
+    /// which the descent went left while descending, instead of recovering it from the index at
+    /// which the descent ended.
+    ///
+    /// This is only kept to check and benchmark `descend` against. The compiler turns the tracking
+    /// into a branch-free descent, which is faster while the collection fits in cache, but much
+    /// slower once it does not.
+    #[cfg(test)]
+    fn descend_tracking<F>(&self, mut i: usize, go_left: F) -> Option<usize>
+    where
+        F: Fn(&T) -> bool,
+    {
+        #[cfg(feature = "nightly")]
+        let (multiplier, offset) = (prefetch_multiplier::<T>(), self.offset);
+
+        let mut last = None;
+        while i < self.items.len() {
+            #[cfg(feature = "nightly")]
+            self.prefetch_below(i, multiplier, offset);
+
+            // in bounds because i < self.items.len()
+            i = if go_left(self.at(i)) {
+                last = Some(i);
+                2 * i + 1
+            } else {
+                2 * i + 2
+            };
+        }
+
+        last
+    }
+
+    /// Prefetch the elements that a descent through index `i` will visit a few levels further
+    /// down, if prefetching is enabled.
+    #[cfg(feature = "nightly")]
+    #[inline(always)]
+    fn prefetch_below(&self, i: usize, multiplier: usize, offset: usize) {
+        if !self.prefetch {
+            return;
+        }
+
+        use prefetch::prefetch::*;
+        // the mask only keeps the index below the next power of two, so the address may be past
+        // the end of the array. the arithmetic therefore wraps, rather than assuming that the
+        // result is in bounds like `offset` or `add` would.
+        if mem::size_of::<T>() <= CACHE_LINE {
+            let j = multiplier.wrapping_mul(i).wrapping_add(offset) & self.mask;
+            // unsafe is safe because pointer is never dereferenced
+            unsafe { prefetch::<Read, High, Data, _>(self.items.as_ptr().wrapping_add(j)) };
+        } else {
+            // no descendants share a cache line, so instead fetch every line of the two
+            // children, which are adjacent and one of which is the next element visited.
+            let lines = (2 * mem::size_of::<T>()).div_ceil(CACHE_LINE);
+            let j = i.wrapping_mul(2).wrapping_add(1) & self.mask;
+            let p = self.items.as_ptr().wrapping_add(j) as *const u8;
+            for line in 0..lines {
+                // unsafe is safe because pointer is never dereferenced
+                unsafe { prefetch::<Read, High, Data, _>(p.wrapping_add(line * CACHE_LINE)) };
+            }
+        }
+    }
+});
+
+impl<T> OrderedCollection<T> {
+    /// Take all the elements out of the collection, in sorted order, leaving it empty.
+    ///
+    /// The prefetch settings of the collection are kept.
+    fn take_sorted(&mut self) -> Vec<T> {
+        let mut v = mem::take(&mut self.items);
+        self.mask = eytzinger_mask(0);
+        eytzinger_unpermute(&mut v);
+        v
+    }
+
+    /// Replace the elements of the collection with those of the sorted vector `v`, which is
+    /// permuted into the search layout in place.
+    ///
+    /// Unlike assigning a freshly constructed collection, this keeps the prefetch settings of the
+    /// collection.
+    fn relayout(&mut self, mut v: Vec<T>) {
+        eytzinger_permute(&mut v);
+        // the collection never grows, so any spare capacity would be wasted
+        v.shrink_to_fit();
+        self.mask = eytzinger_mask(v.len());
+        self.items = v;
+    }
+
+    /// Construct a collection from the sorted vector `v`, with the prefetch settings of `self`.
+    fn relayout_like(&self, v: Vec<T>) -> Self {
+        let mut x = Self::from_layout(Vec::new());
+        x.prefetch = self.prefetch;
+        x.offset = self.offset;
+        x.relayout(v);
+        x
+    }
+
+    /// Returns the number of elements the internal array holds for a collection of `n` elements.
+    ///
+    /// The search layout is a permutation of the elements with no padding, so this is always `n`.
+    /// It is provided so that arrays that mirror the layout, such as one passed to
+    /// `from_eytzinger_vec`, can be sized without depending on that detail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let n = 10;
+    /// let mut v = Vec::with_capacity(OrderedCollection::<u32>::capacity_for(n));
+    /// v.extend(0..n as u32);
+    /// assert_eq!(OrderedCollection::from(v).to_eytzinger_vec().len(), n);
+    /// ```
+    #[must_use]
+    pub fn capacity_for(n: usize) -> usize {
+        n
+    }
+}
+
+any_alloc!(impl<T> {
+    /// Returns the number of elements in the collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// assert_eq!(x.len(), 4);
+    /// ```
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if the collection contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::<u32>::from(vec![]);
+    /// assert!(x.is_empty());
+    /// ```
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+});
+
+impl<T> OrderedCollection<T> {
+    /// Returns the number of elements the collection's allocation can hold.
+    ///
+    /// Collections are normally built without spare capacity, so this is usually `len()`. It can
+    /// be larger for collections built by a [`Rebuilder`], or ones that have been cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// assert_eq!(x.capacity(), 4);
+    /// ```
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.items.capacity()
+    }
+
+    /// Describe how the collection lays out its elements in memory.
+    ///
+    /// The current layout has no padding, so it occupies exactly `len()` slots, aligned as `T`
+    /// is. Spare capacity, as reported by `capacity`, is not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::{LayoutInfo, OrderedCollection};
+    /// let x = OrderedCollection::from(vec![1u64, 2, 4, 8, 16]);
+    /// assert_eq!(
+    ///     x.layout_info(),
+    ///     LayoutInfo {
+    ///         len: 5,
+    ///         slots: 5,
+    ///         align: 8,
+    ///     }
+    /// );
+    /// ```
+    #[must_use]
+    pub fn layout_info(&self) -> LayoutInfo {
+        LayoutInfo {
+            len: self.items.len(),
+            slots: self.items.len(),
+            align: mem::align_of::<T>(),
+        }
+    }
+
+    /// Remove all the elements from the collection, keeping its allocation.
+    ///
+    /// Handing the cleared collection to [`Rebuilder::recycle`] lets the next rebuild reuse the
+    /// allocation, so that a rebuild loop does not need to reallocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// x.clear();
+    /// assert!(x.is_empty());
+    /// assert_eq!(x.capacity(), 4);
+    /// assert_eq!(x.find_gte(0), None);
+    /// ```
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.mask = eytzinger_mask(0);
+    }
+
+    /// Shrink the capacity of the collection's allocation as much as possible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// x.clear();
+    /// x.shrink_to_fit();
+    /// assert_eq!(x.capacity(), 0);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        self.items.shrink_to_fit();
+    }
+
+    /// Shrink the capacity of the collection's allocation, but to no less than `min_capacity`.
+    ///
+    /// The capacity stays at least `len()`. Keeping some spare capacity in a collection that is
+    /// cleared and handed to a [`Rebuilder`] lets later, slightly larger rebuilds avoid
+    /// reallocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut x = OrderedCollection::from((0..100).collect::<Vec<_>>());
+    /// x.clear();
+    /// x.shrink_to(10);
+    /// assert!(x.capacity() >= 10 && x.capacity() < 100);
+    /// ```
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.items.shrink_to(min_capacity);
+    }
+
+    /// Returns the smallest element in the collection, or `None` if it is empty.
+    ///
+    /// The order of the elements is fixed when the collection is constructed, so this is just the
+    /// leftmost element of the search tree. Finding it walks down the leftmost path of the tree,
+    /// and so takes `O(log n)` time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![8, 2, 64, 1, 16]);
+    /// assert_eq!(x.min(), Some(&1));
+    /// ```
+    #[must_use]
+    pub fn min(&self) -> Option<&T> {
+        self.items.get(eytzinger_first(self.items.len()))
+    }
+
+    /// Returns the largest element in the collection, or `None` if it is empty.
+    ///
+    /// Like `min`, this is just the rightmost element of the search tree, and finding it walks
+    /// down the rightmost path of the tree in `O(log n)` time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![8, 2, 64, 1, 16]);
+    /// assert_eq!(x.max(), Some(&64));
+    /// ```
+    #[must_use]
+    pub fn max(&self) -> Option<&T> {
+        self.items.get(eytzinger_last(self.items.len()))
+    }
+
+    /// Returns the `k`-th largest element in the collection, or `None` if there are no more than
+    /// `k` elements.
+    ///
+    /// `nth_from_end(0)` is the largest element, the same as `max`. This is the element at
+    /// position `len() - 1 - k` in sorted order, which takes `O(log n)` time to locate in the
+    /// search layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![8, 2, 64, 1, 16]);
+    /// assert_eq!(x.nth_from_end(0), Some(&64));
+    /// assert_eq!(x.nth_from_end(1), Some(&16));
+    /// assert_eq!(x.nth_from_end(4), Some(&1));
+    /// assert_eq!(x.nth_from_end(5), None);
+    /// ```
+    #[must_use]
+    pub fn nth_from_end(&self, k: usize) -> Option<&T> {
+        let n = self.items.len();
+        if k >= n {
+            return None;
+        }
+        // in bounds because k < n
+        Some(self.at(sorted_to_eytzinger(n - 1 - k, n)))
+    }
+
+    /// Returns the height of the implicit search tree.
+    ///
+    /// This is the largest number of levels a lookup descends through, which is
+    /// `floor(log2(n)) + 1` for a collection of `n > 0` elements, and `0` for an empty collection.
+    /// Lookups whose descent ends above an incomplete bottom level go through one level fewer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert_eq!(x.depth(), 3);
+    /// ```
+    #[must_use]
+    pub fn depth(&self) -> u32 {
+        usize::BITS - self.items.len().leading_zeros()
+    }
+
+    /// Enable or disable prefetching during lookups.
+    ///
+    /// Prefetching is enabled by default. On some CPUs, software prefetching slows lookups down
+    /// rather than speeding them up, and this makes it possible to compare the two without
+    /// recompiling. It only affects the speed of lookups, not their results, and it only has an
+    /// effect with the (non-default) `nightly` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// x.set_prefetch_enabled(false);
+    /// assert!(!x.prefetch_enabled());
+    /// assert_eq!(x.find_gte(3), Some(&4));
+    /// ```
+    pub fn set_prefetch_enabled(&mut self, enabled: bool) {
+        self.prefetch = enabled;
+    }
+
+    /// Returns `true` if lookups prefetch.
+    ///
+    /// See `set_prefetch_enabled`.
+    #[must_use]
+    pub fn prefetch_enabled(&self) -> bool {
+        self.prefetch
+    }
+
+    /// Copy the elements of the collection into a vector, in the order of the search layout.
+    ///
+    /// The elements are in Eytzinger order: the element at index `i` is smaller than or equal to
+    /// the elements in the subtree rooted at index `2i + 2`, and greater than or equal to the
+    /// elements in the subtree rooted at index `2i + 1`. The layout is fully determined by the
+    /// elements and their number, so the vector can be searched by anything that implements the
+    /// same descent, such as another process that was handed the vector. The prefetch mask is
+    /// derived from the length of the vector, so no other information is needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 3]);
+    /// assert_eq!(x.to_eytzinger_vec(), [2, 1, 3]);
+    /// ```
+    #[must_use]
+    pub fn to_eytzinger_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.items.clone()
+    }
+
+    /// Returns the elements in the order of the search layout, along with their number.
+    ///
+    /// This is what a serialization format needs to store a collection and later restore it with
+    /// `from_raw_parts`, without sorting or rearranging the elements on either side. The slice is
+    /// in the same order as `to_eytzinger_vec`, and always holds `n` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 3]);
+    /// assert_eq!(x.raw_parts(), (&[2, 1, 3][..], 3));
+    /// ```
+    #[must_use]
+    pub fn raw_parts(&self) -> (&[T], usize) {
+        (&self.items, self.items.len())
+    }
+
+    /// Construct a collection of references to the elements of this collection.
+    ///
+    /// References are ordered like the elements they point to, so the new collection keeps the
+    /// existing layout, and is built in a single pass without sorting. Lookups on it can return
+    /// references tied to `self` with `find_gte_deref`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![String::from("b"), String::from("d")]);
+    /// let r = x.as_ref_collection();
+    /// assert_eq!(r.find_gte_deref(String::from("c")), Some(&String::from("d")));
+    /// ```
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn as_ref_collection(&self) -> OrderedCollection<&T> {
+        let mut x = OrderedCollection::from_layout(self.items.iter().collect());
+        x.prefetch = self.prefetch;
+        x.offset = self.offset;
+        x
+    }
+
+    /// Returns `true` if the two collections hold the same distinct elements, regardless of how
+    /// many times each occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::from(vec![1, 1, 2]);
+    /// let b = OrderedCollection::from(vec![1, 2, 2]);
+    /// assert!(a != b);
+    /// assert!(a.eq_as_set(&b));
+    /// ```
+    #[must_use]
+    pub fn eq_as_set(&self, other: &Self) -> bool
+    where
+        T: Eq,
+    {
+        fn distinct<T: Eq>(items: &[T]) -> impl Iterator<Item = &T> {
+            let mut prev = None;
+            InOrder::new(items).filter(move |&e| {
+                let new = prev != Some(e);
+                prev = Some(e);
+                new
+            })
+        }
+        distinct(&self.items).eq(distinct(&other.items))
+    }
+
+    /// Iterate over all the elements, in sorted order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![9, 1, 4]);
+    /// assert_eq!(x.iter().collect::<Vec<_>>(), [&1, &4, &9]);
+    /// ```
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn iter(&self) -> SortedIter<'_, T> {
+        SortedIter::ranks(&self.items, 0, self.items.len())
+    }
+
+    /// Remove all the elements from the collection, and iterate over them in sorted order.
+    ///
+    /// The collection is left empty, and can be reused, for example as the target of a later
+    /// `extend_from_sorted`. The elements are removed as soon as this is called, even if the
+    /// returned iterator is not consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut x = OrderedCollection::from(vec![9, 1, 4]);
+    /// assert_eq!(x.drain().collect::<Vec<_>>(), [1, 4, 9]);
+    /// assert!(x.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> impl ExactSizeIterator<Item = T> {
+        self.take_sorted().into_iter()
+    }
+
+    /// Iterate over every pair of adjacent elements, in sorted order.
+    ///
+    /// A collection of `n > 0` elements yields `n - 1` pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![9, 1, 4]);
+    /// let pairs: Vec<_> = x.sorted_windows().collect();
+    /// assert_eq!(pairs, [(&1, &4), (&4, &9)]);
+    /// ```
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn sorted_windows(&self) -> impl Iterator<Item = (&T, &T)> {
+        InOrder::new(&self.items).zip(InOrder::new(&self.items).skip(1))
+    }
+
+    /// Check the internal invariants of the collection, and return `true` if they all hold.
+    ///
+    /// This verifies that the elements form a valid search layout of a sorted sequence (that is,
+    /// that walking the layout in order never yields an element smaller than the one before it),
+    /// and that the lookup mask matches the number of elements. It takes `O(n)` time, and is
+    /// meant as a cheap assertion in tests after building a collection through one of the
+    /// unchecked constructors, such as `from_sorted_iter` or `from_eytzinger_vec`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from_sorted_iter(vec![7, 12, 42, 89]);
+    /// assert!(x.debug_validate());
+    ///
+    /// let y = OrderedCollection::from_sorted_iter(vec![12, 7, 42, 89]);
+    /// assert!(!y.debug_validate());
+    /// ```
+    #[must_use]
+    pub fn debug_validate(&self) -> bool
+    where
+        T: Ord,
+    {
+        self.mask == eytzinger_mask(self.items.len()) && self.sorted_windows().all(|(a, b)| a <= b)
+    }
+}
+
+impl<'a, T: Ord> OrderedCollection<&'a T> {
+    /// Find the smallest value `v` such that `v >= x`, and return the reference to it that is
+    /// stored in the collection.
+    ///
+    /// This is like `find_gte`, except that it avoids the `&&T` that results from searching a
+    /// collection of references, such as one constructed with `from_slice`. The returned
+    /// reference is also tied to the underlying data, rather than to the collection.
+    ///
+    /// Returns `None` if there is no such `v`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut vals = [42, 89, 7, 12];
+    /// let a = OrderedCollection::from_slice(&mut vals);
+    /// assert_eq!(a.find_gte_deref(50), Some(&89));
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte_deref<X>(&self, x: X) -> Option<&'a T>
+    where
+        &'a T: Borrow<X>,
+        X: Ord,
+    {
+        self.find_gte(x).cloned()
+    }
+}
+
+/// An `OrderedCollection` that can also provide its elements as a sorted slice.
+///
+/// The slice is built on the first call to `sorted_cache`, and kept for the lifetime of the
+/// wrapper, so it is effectively a second copy of every element. Collections that never need the
+/// slice should not be wrapped; a plain `OrderedCollection` holds no cache. Lookups go through to
+/// the wrapped collection, which can be taken back out with `into_inner` to modify it.
+///
+/// # Examples
+///
+/// ```
+/// # use ordsearch::{CachedOrderedCollection, OrderedCollection};
+/// let x = CachedOrderedCollection::new(OrderedCollection::from(vec![42, 89, 7, 12]));
+/// assert_eq!(x.find_gte(50), Some(&89));
+/// assert_eq!(x.sorted_cache(), [7, 12, 42, 89]);
+/// ```
+pub struct CachedOrderedCollection<T> {
+    inner: OrderedCollection<T>,
+
+    // the elements in sorted order, materialized on the first call to `sorted_cache`. this is a
+    // `OnceLock` rather than a `OnceCell` so that the wrapper stays `Sync`.
+    sorted: OnceLock<Vec<T>>,
+}
+
+impl<T> CachedOrderedCollection<T> {
+    /// Wrap a collection. The sorted slice is not built until it is first asked for.
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn new(inner: OrderedCollection<T>) -> Self {
+        CachedOrderedCollection {
+            inner,
+            sorted: OnceLock::new(),
+        }
+    }
+
+    /// Returns the elements of the collection as a slice, in sorted order.
+    ///
+    /// The slice is built on the first call and kept for all later calls, so repeated calls are
+    /// cheap.
+    #[must_use]
+    pub fn sorted_cache(&self) -> &[T]
+    where
+        T: Clone,
+    {
+        self.sorted
+            .get_or_init(|| InOrder::new(&self.inner.items).cloned().collect())
+    }
+
+    /// Returns the number of elements in the sorted copy, which is a separate allocation from the
+    /// search layout that `layout_info` describes.
+    ///
+    /// This is `0` until `sorted_cache` is first called, and `len()` after.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::{CachedOrderedCollection, OrderedCollection};
+    /// let x = CachedOrderedCollection::new(OrderedCollection::from(vec![1, 2, 4]));
+    /// assert_eq!(x.cached_len(), 0);
+    /// let _ = x.sorted_cache();
+    /// assert_eq!(x.cached_len(), 3);
+    /// ```
+    #[must_use]
+    pub fn cached_len(&self) -> usize {
+        self.sorted.get().map_or(0, Vec::len)
+    }
+
+    /// Unwrap the collection, dropping the sorted copy if it was built.
+    #[must_use]
+    pub fn into_inner(self) -> OrderedCollection<T> {
+        self.inner
+    }
+}
+
+impl<T> Deref for CachedOrderedCollection<T> {
+    type Target = OrderedCollection<T>;
+
+    fn deref(&self) -> &OrderedCollection<T> {
+        &self.inner
+    }
+}
+
+impl<T> From<OrderedCollection<T>> for CachedOrderedCollection<T> {
+    /// Wrap a collection. See `CachedOrderedCollection::new`.
+    fn from(inner: OrderedCollection<T>) -> Self {
+        Self::new(inner)
+    }
+}
+
+/// A collection of items ordered by a comparator chosen at runtime.
+///
+/// This is like `OrderedCollection`, except that the ordering of the elements is determined by a
+/// boxed comparator rather than by `T: Ord`. This makes it possible to pick an ordering at
+/// runtime without a separate monomorphized type for every ordering, at the cost of a dynamic
+/// call for every comparison.
+///
+/// # Examples
+///
+/// ```
+/// # use ordsearch::OrderedCollectionDyn;
+/// let x = OrderedCollectionDyn::new(vec![1, 2, 4, 8, 16, 32, 64], |a: &i32, b: &i32| b.cmp(a));
+/// assert_eq!(x.find_gte(&10), Some(&8));
+/// assert_eq!(x.find_gte(&0), None);
+/// ```
+pub struct OrderedCollectionDyn<T> {
+    inner: OrderedCollection<T>,
+    cmp: Comparator<T>,
+}
+
+/// A comparator chosen at runtime.
+type Comparator<T> = Box<dyn Fn(&T, &T) -> Ordering>;
+
+impl<T> OrderedCollectionDyn<T> {
+    /// Construct a new `OrderedCollectionDyn` from a vector of elements, ordered by `cmp`.
+    ///
+    /// `cmp` must be a total order, as if it was the `Ord` implementation of `T`. It is used both
+    /// to sort the elements, and for every lookup.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollectionDyn;
+    /// let x = OrderedCollectionDyn::new(vec!["b", "ccc", "aa"], |a: &&str, b: &&str| {
+    ///     a.len().cmp(&b.len())
+    /// });
+    /// assert_eq!(x.find_gte(&"zz"), Some(&"aa"));
+    /// ```
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn new<F>(mut v: Vec<T>, cmp: F) -> Self
+    where
+        F: Fn(&T, &T) -> Ordering + 'static,
+    {
+        v.sort_unstable_by(&cmp);
+        let n = v.len();
+        OrderedCollectionDyn {
+            inner: OrderedCollection::from_layout(eytzinger_layout(v.into_iter(), n)),
+            cmp: Box::new(cmp),
+        }
+    }
+
+    /// Find the smallest value `v` such that `v >= x` according to the collection's comparator.
+    ///
+    /// Returns `None` if there is no such `v`.
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte(&self, x: &T) -> Option<&T> {
+        // in bounds because descend only returns valid indices
+        self.inner
+            .descend(0, |e| (self.cmp)(x, e) != Ordering::Greater)
+            .map(|j| self.inner.at(j))
+    }
+
+    /// Returns the number of elements in the collection.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the collection contains no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+/// A map from keys to values, with efficient lower-bound lookups on the keys.
+///
+/// This is like an `OrderedCollection` of key-value pairs that is ordered by key only, so the
+/// values do not have to implement `Ord`. Keys are expected to be unique.
+///
+/// # Examples
+///
+/// ```
+/// # use std::collections::BTreeMap;
+/// # use ordsearch::OrderedMap;
+/// let mut m = BTreeMap::new();
+/// m.insert(4, "four");
+/// m.insert(1, "one");
+/// m.insert(9, "nine");
+/// let x = OrderedMap::from_btreemap(m);
+/// assert_eq!(x.get(&4), Some(&"four"));
+/// assert_eq!(x.find_gte(&2), Some((&4, &"four")));
+/// ```
+pub struct OrderedMap<K, V> {
+    inner: OrderedCollection<(K, V)>,
+}
+
+impl<K: Ord, V> OrderedMap<K, V> {
+    /// Construct a new `OrderedMap` from a `BTreeMap`.
+    ///
+    /// The entries of a `BTreeMap` are already sorted by key, so they are moved into place in a
+    /// single pass without sorting them again.
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn from_btreemap(m: BTreeMap<K, V>) -> Self {
+        let n = m.len();
+        OrderedMap {
+            inner: OrderedCollection::from_layout(eytzinger_layout(m.into_iter(), n)),
+        }
+    }
+
+    /// Find the entry with the smallest key `k` such that `k >= key`.
+    ///
+    /// Returns `None` if there is no such entry.
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        // in bounds because descend only returns valid indices
+        self.inner.descend(0, |e| key <= e.0.borrow()).map(|j| {
+            let e = self.inner.at(j);
+            (&e.0, &e.1)
+        })
+    }
+
+    /// Find the entry with the smallest key `k` such that `k >= key`, and return it as an
+    /// [`Entry`].
+    ///
+    /// This is like `find_gte`, for when it is more convenient to keep the key and the value
+    /// together. Returns `None` if there is no such entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::collections::BTreeMap;
+    /// # use ordsearch::OrderedMap;
+    /// let mut m = BTreeMap::new();
+    /// m.insert(4, "four");
+    /// m.insert(9, "nine");
+    /// let x = OrderedMap::from_btreemap(m);
+    /// let e = x.get_gte_entry(&5).unwrap();
+    /// assert_eq!((e.key(), e.value()), (&9, &"nine"));
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn get_gte_entry<Q>(&self, key: &Q) -> Option<Entry<'_, K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find_gte(key).map(|(key, value)| Entry { key, value })
+    }
+
+    /// Find the entry with the smallest key `k` such that `k >= key`, and return it as an
+    /// [`EntryMut`], through which its value can be modified.
+    ///
+    /// The key cannot be modified, since that could change the order of the entries. Returns
+    /// `None` if there is no such entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::collections::BTreeMap;
+    /// # use ordsearch::OrderedMap;
+    /// let mut m = BTreeMap::new();
+    /// m.insert(4, 40);
+    /// m.insert(9, 90);
+    /// let mut x = OrderedMap::from_btreemap(m);
+    /// *x.get_gte_entry_mut(&5).unwrap().value_mut() += 1;
+    /// assert_eq!(x.get(&9), Some(&91));
+    /// ```
+    #[must_use = "the returned entry must be used to modify the value"]
+    pub fn get_gte_entry_mut<Q>(&mut self, key: &Q) -> Option<EntryMut<'_, K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let j = self.inner.descend(0, |e| key <= e.0.borrow())?;
+        // in bounds because descend only returns valid indices
+        let e = self.inner.at_mut(j);
+        Some(EntryMut {
+            key: &e.0,
+            value: &mut e.1,
+        })
+    }
+
+    /// Returns the value for the key equal to `key`.
+    ///
+    /// Returns `None` if there is no such key.
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.find_gte(key) {
+            Some((k, v)) if k.borrow() == key => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of entries in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the map contains no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl<K: Ord, V> From<BTreeMap<K, V>> for OrderedMap<K, V> {
+    /// Construct a new `OrderedMap` from a `BTreeMap`. See `OrderedMap::from_btreemap`.
+    fn from(m: BTreeMap<K, V>) -> Self {
+        Self::from_btreemap(m)
+    }
+}
+
+/// An entry of an [`OrderedMap`], returned by `OrderedMap::get_gte_entry`.
+#[derive(Debug, Clone, Copy)]
+pub struct Entry<'a, K: 'a, V: 'a> {
+    key: &'a K,
+    value: &'a V,
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    /// Returns the key of the entry.
+    #[must_use]
+    pub fn key(&self) -> &'a K {
+        self.key
+    }
+
+    /// Returns the value of the entry.
+    #[must_use]
+    pub fn value(&self) -> &'a V {
+        self.value
+    }
+}
+
+/// An entry of an [`OrderedMap`] whose value can be modified, returned by
+/// `OrderedMap::get_gte_entry_mut`.
+#[derive(Debug)]
+pub struct EntryMut<'a, K: 'a, V: 'a> {
+    key: &'a K,
+    value: &'a mut V,
+}
+
+impl<'a, K, V> EntryMut<'a, K, V> {
+    /// Returns the key of the entry.
+    #[must_use]
+    pub fn key(&self) -> &K {
+        self.key
+    }
+
+    /// Returns the value of the entry.
+    #[must_use]
+    pub fn value(&self) -> &V {
+        self.value
+    }
+
+    /// Returns a mutable reference to the value of the entry.
+    #[must_use]
+    pub fn value_mut(&mut self) -> &mut V {
+        self.value
+    }
+
+    /// Converts the entry into a mutable reference to its value, with the lifetime of the map.
+    #[must_use]
+    pub fn into_value_mut(self) -> &'a mut V {
+        self.value
+    }
+}
+
+/// A type that is ordered by a key stored within it.
+///
+/// Implement this to store records in an [`OrderedCollectionByKey`], which orders and searches
+/// them by their key alone.
+pub trait Keyed {
+    /// The key that records are ordered by.
+    type Key: Ord;
+
+    /// Returns the key of this record.
+    fn key(&self) -> &Self::Key;
+}
+
+/// A collection of records ordered by their [`Keyed::key`].
+///
+/// This is like `OrderedCollection`, except that the elements are compared by their key rather
+/// than through `T: Ord`, so the rest of a record does not need to be comparable. Unlike
+/// `OrderedCollectionDyn`, the comparison is resolved at compile time, so it costs no more than
+/// comparing the keys directly.
+///
+/// # Examples
+///
+/// ```
+/// # use ordsearch::{Keyed, OrderedCollectionByKey};
+/// struct Event {
+///     time: u64,
+///     name: &'static str,
+/// }
+///
+/// impl Keyed for Event {
+///     type Key = u64;
+///     fn key(&self) -> &u64 {
+///         &self.time
+///     }
+/// }
+///
+/// let x = OrderedCollectionByKey::new(vec![
+///     Event { time: 30, name: "c" },
+///     Event { time: 10, name: "a" },
+///     Event { time: 20, name: "b" },
+/// ]);
+/// assert_eq!(x.find_gte(&15).map(|e| e.name), Some("b"));
+/// assert!(x.find_gte(&31).is_none());
+/// ```
+pub struct OrderedCollectionByKey<T> {
+    inner: OrderedCollection<T>,
+}
+
+impl<T: Keyed> OrderedCollectionByKey<T> {
+    /// Construct a new `OrderedCollectionByKey` from a vector of records.
+    ///
+    /// The records are sorted by key, which takes `O(n log n)` time.
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn new(mut v: Vec<T>) -> Self {
+        v.sort_unstable_by(|a, b| a.key().cmp(b.key()));
+        let n = v.len();
+        OrderedCollectionByKey {
+            inner: OrderedCollection::from_layout(eytzinger_layout(v.into_iter(), n)),
+        }
+    }
+
+    /// Find the record with the smallest key `k` such that `k >= key`.
+    ///
+    /// Returns `None` if there is no such record.
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte<Q>(&self, key: &Q) -> Option<&T>
+    where
+        T::Key: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        // in bounds because descend only returns valid indices
+        self.inner
+            .descend(0, |e| key <= e.key().borrow())
+            .map(|j| self.inner.at(j))
+    }
+
+    /// Returns the number of records in the collection.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the collection contains no records.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl<T: Keyed> From<Vec<T>> for OrderedCollectionByKey<T> {
+    /// Construct a new `OrderedCollectionByKey` from a vector of records. See
+    /// `OrderedCollectionByKey::new`.
+    fn from(v: Vec<T>) -> Self {
+        Self::new(v)
+    }
+}
+
+/// A collection of strings stored in a single allocation, with efficient lower-bound lookups.
+///
+/// An `OrderedCollection<String>` stores a pointer to a separate heap allocation for every
+/// string, so each comparison during a lookup has to chase that pointer to wherever the string
+/// happens to live in memory. `OrderedStrings` instead copies the bytes of all the strings into
+/// one buffer, and searches a layout of offsets into that buffer. The buffer holds the strings in
+/// the same order as the layout, so the strings compared against near the top of the tree, which
+/// every lookup visits, are packed together.
+///
+/// # Examples
+///
+/// ```
+/// # use ordsearch::OrderedStrings;
+/// let x = OrderedStrings::new(vec![
+///     "banana".to_string(),
+///     "apple".to_string(),
+///     "cherry".to_string(),
+/// ]);
+/// assert_eq!(x.find_gte("b"), Some("banana"));
+/// assert_eq!(x.find_gte("cherry"), Some("cherry"));
+/// assert_eq!(x.find_gte("date"), None);
+/// ```
+pub struct OrderedStrings {
+    bytes: String,
+    inner: OrderedCollection<(usize, usize)>,
+}
+
+impl OrderedStrings {
+    /// Construct a new `OrderedStrings` from a vector of strings.
+    ///
+    /// The strings are sorted, which takes `O(n log n)` time, and are then copied into a single
+    /// buffer.
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn new(mut v: Vec<String>) -> Self {
+        v.sort_unstable();
+        let n = v.len();
+        let mut bytes = String::with_capacity(v.iter().map(String::len).sum());
+        let mut offsets = Vec::with_capacity(n);
+        for s in eytzinger_layout(v.into_iter(), n) {
+            let start = bytes.len();
+            bytes.push_str(&s);
+            offsets.push((start, bytes.len()));
+        }
+        OrderedStrings {
+            bytes,
+            inner: OrderedCollection::from_layout(offsets),
+        }
+    }
+
+    /// Get the string stored at the given offsets into the buffer.
+    ///
+    /// The offsets must be ones produced by `new`. This is only checked if the `safe` feature is
+    /// enabled.
+    #[inline(always)]
+    fn str_at(&self, &(start, end): &(usize, usize)) -> &str {
+        #[cfg(feature = "safe")]
+        {
+            &self.bytes[start..end]
+        }
+        #[cfg(not(feature = "safe"))]
+        // safe because new only records offsets that are in bounds and on character boundaries
+        unsafe {
+            self.bytes.get_unchecked(start..end)
+        }
+    }
+
+    /// Find the smallest string `s` such that `s >= x`.
+    ///
+    /// Since every string that starts with `x` is ordered at or after `x`, and before any string
+    /// that does not start with `x` but is greater than it, this also finds the first string with
+    /// `x` as a prefix, if there is one.
+    ///
+    /// Returns `None` if there is no such `s`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedStrings;
+    /// let x = OrderedStrings::from(vec!["foo".to_string(), "foobar".to_string(), "fop".to_string()]);
+    /// assert_eq!(x.find_gte("foob"), Some("foobar"));
+    /// assert_eq!(x.find_gte("foo"), Some("foo"));
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    pub fn find_gte(&self, x: &str) -> Option<&str> {
+        // in bounds because descend only returns valid indices
+        self.inner
+            .descend(0, |e| x <= self.str_at(e))
+            .map(|j| self.str_at(self.inner.at(j)))
+    }
+
+    /// Returns the number of strings in the collection.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the collection contains no strings.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl From<Vec<String>> for OrderedStrings {
+    /// Construct a new `OrderedStrings` from a vector of strings. See `OrderedStrings::new`.
+    fn from(v: Vec<String>) -> Self {
+        Self::new(v)
+    }
+}
+
+/// An `f64` that is totally ordered, so that it can be stored in an `OrderedCollection`.
+///
+/// The ordering is that of [`f64::total_cmp`]. In particular, `-0.0` is ordered before `0.0`,
+/// NaNs with the sign bit cleared (such as `f64::NAN`) are ordered after positive infinity, and
+/// NaNs with the sign bit set are ordered before negative infinity. Two values are only equal if
+/// they have the same bit pattern.
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct OrderedF64(pub f64);
+
+impl PartialEq for OrderedF64 {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrderedF64 {}
+
+impl PartialOrd for OrderedF64 {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedF64 {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl OrderedCollection<OrderedF64> {
+    /// Construct a new `OrderedCollection` from a vector of floating-point numbers.
+    ///
+    /// The numbers are ordered as described for [`OrderedF64`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::from_f64(vec![4.2, 8.9, -0.7, 1.2]);
+    /// assert_eq!(a.find_gte_f64(5.0), Some(&8.9));
+    /// ```
+    #[must_use = "constructing a collection has no effect unless it is used"]
+    pub fn from_f64(v: Vec<f64>) -> Self {
+        Self::from(v.into_iter().map(OrderedF64).collect::<Vec<_>>())
+    }
+
+    /// Find the smallest value `v` such that `v >= x`.
+    ///
+    /// The numbers are compared as described for [`OrderedF64`]. Returns `None` if there is no
+    /// such `v`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::from_f64(vec![1.0, 2.0, 4.0, 8.0]);
+    /// assert_eq!(a.find_gte_f64(2.5), Some(&4.0));
+    /// assert_eq!(a.find_gte_f64(8.5), None);
+    /// ```
+    #[must_use = "this returns the result of the lookup without modifying the collection"]
+    #[inline]
+    pub fn find_gte_f64(&self, x: f64) -> Option<&f64> {
+        self.find_gte(OrderedF64(x)).map(|v| &v.0)
+    }
+}
+
+#[cfg(feature = "nightly")]
+macro_rules! simd_find_gte_many {
+    ($($t:ty),*) => {$(
+        impl OrderedCollection<$t> {
+            /// Find the smallest value `v` such that `v >= x`, for every `x` in `xs`.
+            ///
+            /// The queries are processed eight at a time, with each one in a separate SIMD lane.
+            /// Every step of the descent advances all eight queries one level down the tree using
+            /// a single gathered load, which lets the loads of the different queries overlap. This
+            /// increases throughput for large batches over data that does not fit in cache.
+            ///
+            /// This method is only available with the (non-default) `nightly` feature.
+            #[must_use = "this returns the result of the lookup without modifying the collection"]
+            pub fn find_gte_many(&self, xs: &[$t]) -> Vec<Option<&$t>> {
+                use std::simd::prelude::*;
+
+                const LANES: usize = 8;
+                let n = Simd::splat(self.items.len());
+                let mut out = Vec::with_capacity(xs.len());
+                let mut chunks = xs.chunks_exact(LANES);
+                for chunk in &mut chunks {
+                    let x = Simd::<$t, LANES>::from_slice(chunk);
+                    let mut i = Simd::<usize, LANES>::splat(0);
+                    for _ in 0..self.depth() {
+                        // the bottom level may be incomplete, so some lanes may finish a level early
+                        let live = i.simd_lt(n);
+                        let v = Simd::gather_select(&self.items, live, i, Simd::splat(0));
+                        let left = x.simd_le(v).cast::<isize>();
+                        let next = i * Simd::splat(2) + left.select(Simd::splat(1), Simd::splat(2));
+                        i = live.select(next, i);
+                    }
+
+                    // in bounds because eytzinger_last_left only returns indices on the search path
+                    out.extend(i.to_array().iter().map(|&i| {
+                        eytzinger_last_left(i).map(|j| self.at(j))
+                    }));
+                }
+                out.extend(chunks.remainder().iter().map(|&x| self.find_gte(x)));
+                out
+            }
+        }
+    )*}
+}
+
+#[cfg(feature = "nightly")]
+simd_find_gte_many!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BudgetExceeded, BuildStats, CachedOrderedCollection, Entry, Keyed, LayoutInfo,
+        OrderedCollection, OrderedCollectionByKey, OrderedCollectionDyn, OrderedF64, OrderedMap,
+        OrderedStrings, Rebuilder, SearchResult, Sorted, SortedIter,
+    };
+
+    /// Bound the size of exhaustive tests, which are too slow to run in full under miri.
+    fn sizes(n: usize) -> usize {
+        if cfg!(miri) {
+            n.min(12)
+        } else {
+            n
+        }
+    }
+
+    #[test]
+    fn complete_exact() {
+        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+        assert_eq!(x.find_gte(1), Some(&1));
+        assert_eq!(x.find_gte(2), Some(&2));
+        assert_eq!(x.find_gte(4), Some(&4));
+        assert_eq!(x.find_gte(8), Some(&8));
+        assert_eq!(x.find_gte(16), Some(&16));
+        assert_eq!(x.find_gte(32), Some(&32));
+        assert_eq!(x.find_gte(64), Some(&64));
+    }
+
+    #[test]
+    fn complete_approximate() {
+        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+        assert_eq!(x.find_gte(0), Some(&1));
+        assert_eq!(x.find_gte(3), Some(&4));
+        assert_eq!(x.find_gte(5), Some(&8));
+        assert_eq!(x.find_gte(6), Some(&8));
+        assert_eq!(x.find_gte(7), Some(&8));
+        for i in 9..16 {
+            assert_eq!(x.find_gte(i), Some(&16));
+        }
+        for i in 17..32 {
+            assert_eq!(x.find_gte(i), Some(&32));
+        }
+        for i in 33..64 {
+            assert_eq!(x.find_gte(i), Some(&64));
+        }
+        assert_eq!(x.find_gte(65), None);
+    }
+
+    #[test]
+    fn unbalanced_exact() {
+        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64, 128, 256]);
+        assert_eq!(x.find_gte(1), Some(&1));
+        assert_eq!(x.find_gte(2), Some(&2));
+        assert_eq!(x.find_gte(4), Some(&4));
+        assert_eq!(x.find_gte(8), Some(&8));
+        assert_eq!(x.find_gte(16), Some(&16));
+        assert_eq!(x.find_gte(32), Some(&32));
+        assert_eq!(x.find_gte(64), Some(&64));
+        assert_eq!(x.find_gte(128), Some(&128));
+        assert_eq!(x.find_gte(256), Some(&256));
+    }
+
+    #[test]
+    fn unbalanced_approximate() {
+        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64, 128, 256]);
+        assert_eq!(x.find_gte(0), Some(&1));
+        assert_eq!(x.find_gte(3), Some(&4));
+        assert_eq!(x.find_gte(5), Some(&8));
         assert_eq!(x.find_gte(6), Some(&8));
         assert_eq!(x.find_gte(7), Some(&8));
         for i in 9..16 {
             assert_eq!(x.find_gte(i), Some(&16));
         }
-        for i in 17..32 {
-            assert_eq!(x.find_gte(i), Some(&32));
+        for i in 17..32 {
+            assert_eq!(x.find_gte(i), Some(&32));
+        }
+        for i in 33..64 {
+            assert_eq!(x.find_gte(i), Some(&64));
+        }
+        for i in 65..128 {
+            assert_eq!(x.find_gte(i), Some(&128));
+        }
+        for i in 129..256 {
+            assert_eq!(x.find_gte(i), Some(&256));
+        }
+        assert_eq!(x.find_gte(257), None);
+    }
+
+    #[test]
+    fn single_element() {
+        let x = OrderedCollection::from(vec![5]);
+        assert_eq!(x.find_gte(4), Some(&5));
+        assert_eq!(x.find_gte(5), Some(&5));
+        assert_eq!(x.find_gte(6), None);
+        assert_eq!(x.find_gt(4), Some(&5));
+        assert_eq!(x.find_gt(5), None);
+    }
+
+    #[test]
+    fn two_elements() {
+        let x = OrderedCollection::from(vec![9, 5]);
+        assert_eq!(x.find_gte(4), Some(&5));
+        assert_eq!(x.find_gte(5), Some(&5));
+        assert_eq!(x.find_gte(6), Some(&9));
+        assert_eq!(x.find_gte(8), Some(&9));
+        assert_eq!(x.find_gte(9), Some(&9));
+        assert_eq!(x.find_gte(10), None);
+        assert_eq!(x.find_gt(4), Some(&5));
+        assert_eq!(x.find_gt(5), Some(&9));
+        assert_eq!(x.find_gt(8), Some(&9));
+        assert_eq!(x.find_gt(9), None);
+    }
+
+    #[test]
+    fn intersection_disjoint() {
+        let a = OrderedCollection::from(vec![1, 3, 5, 7]);
+        let b = OrderedCollection::from(vec![0, 2, 4, 6, 8]);
+        assert_eq!(a.intersection(&b).next(), None);
+        assert_eq!(b.intersection(&a).next(), None);
+    }
+
+    #[test]
+    fn intersection_overlapping() {
+        let a = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+        let b = OrderedCollection::from(vec![2, 3, 4, 5, 16, 17, 128]);
+        let both: Vec<_> = a.intersection(&b).cloned().collect();
+        assert_eq!(both, vec![2, 4, 16]);
+        let both: Vec<_> = b.intersection(&a).cloned().collect();
+        assert_eq!(both, vec![2, 4, 16]);
+    }
+
+    #[test]
+    fn intersection_identical() {
+        let a = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64, 128, 256]);
+        let both: Vec<_> = a.intersection(&a).cloned().collect();
+        assert_eq!(both, vec![1, 2, 4, 8, 16, 32, 64, 128, 256]);
+    }
+
+    #[test]
+    fn intersection_duplicates() {
+        let a = OrderedCollection::from(vec![1, 1, 1, 2, 3, 3]);
+        let b = OrderedCollection::from(vec![1, 1, 3, 3, 3]);
+        let both: Vec<_> = a.intersection(&b).cloned().collect();
+        assert_eq!(both, vec![1, 1, 3, 3]);
+    }
+
+    #[test]
+    fn union_matches_btreeset() {
+        use std::collections::BTreeSet;
+
+        let av = vec![1, 2, 4, 8, 16, 32, 64];
+        let bv = vec![2, 3, 4, 5, 16, 17, 128];
+        let a = OrderedCollection::from(av.clone());
+        let b = OrderedCollection::from(bv.clone());
+        let aset: BTreeSet<_> = av.into_iter().collect();
+        let bset: BTreeSet<_> = bv.into_iter().collect();
+
+        let either: Vec<_> = a.union(&b).collect();
+        let expected: Vec<_> = aset.union(&bset).collect();
+        assert_eq!(either, expected);
+        let either: Vec<_> = b.union(&a).collect();
+        assert_eq!(either, expected);
+        assert_eq!(a.union(&a).count(), a.items.len());
+    }
+
+    #[test]
+    fn union_duplicates() {
+        let a = OrderedCollection::from(vec![1, 1, 1, 2, 3, 3]);
+        let b = OrderedCollection::from(vec![0, 1, 3, 3, 3, 4, 4]);
+        let either: Vec<_> = a.union(&b).cloned().collect();
+        assert_eq!(either, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn difference_matches_btreeset() {
+        use std::collections::BTreeSet;
+
+        let av = vec![1, 2, 4, 8, 16, 32, 64];
+        let bv = vec![2, 3, 4, 5, 16, 17, 128];
+        let a = OrderedCollection::from(av.clone());
+        let b = OrderedCollection::from(bv.clone());
+        let aset: BTreeSet<_> = av.into_iter().collect();
+        let bset: BTreeSet<_> = bv.into_iter().collect();
+
+        let only_a: Vec<_> = a.difference(&b).collect();
+        let expected: Vec<_> = aset.difference(&bset).collect();
+        assert_eq!(only_a, expected);
+        let only_b: Vec<_> = b.difference(&a).collect();
+        let expected: Vec<_> = bset.difference(&aset).collect();
+        assert_eq!(only_b, expected);
+        assert_eq!(a.difference(&a).next(), None);
+    }
+
+    #[test]
+    fn difference_duplicates() {
+        let a = OrderedCollection::from(vec![1, 1, 1, 2, 3, 3, 5, 5]);
+        let b = OrderedCollection::from(vec![1, 3, 4]);
+        let only_a: Vec<_> = a.difference(&b).cloned().collect();
+        assert_eq!(only_a, vec![2, 5, 5]);
+    }
+
+    #[test]
+    fn contains_all_present() {
+        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64, 128, 256]);
+        assert!(x.contains_all(&[1, 2, 4, 8, 16, 32, 64, 128, 256]));
+        assert!(x.contains_all(&[1, 256]));
+        assert!(x.contains_all(&[4, 4, 16, 128]));
+    }
+
+    #[test]
+    fn contains_all_missing() {
+        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64, 128, 256]);
+        assert!(!x.contains_all(&[0]));
+        assert!(!x.contains_all(&[1, 3]));
+        assert!(!x.contains_all(&[2, 4, 8, 17]));
+        assert!(!x.contains_all(&[256, 257]));
+    }
+
+    #[test]
+    fn contains_all_empty() {
+        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+        assert!(x.contains_all::<i32>(&[]));
+        let x = OrderedCollection::<i32>::from(vec![]);
+        assert!(x.contains_all::<i32>(&[]));
+        assert!(!x.contains_all(&[1]));
+    }
+
+    #[test]
+    fn contains_all_exhaustive() {
+        for n in 0..sizes(64) {
+            let x = OrderedCollection::from((0..n).map(|i| 2 * i).collect::<Vec<_>>());
+            for start in 0..2 * n + 1 {
+                let evens: Vec<_> = (start..2 * n).filter(|i| i % 2 == 0).collect();
+                assert!(x.contains_all(&evens));
+                let mut odd = evens.clone();
+                odd.push(2 * n + 1);
+                assert!(!x.contains_all(&odd));
+                if n > 0 {
+                    let mut one_odd = evens.clone();
+                    one_odd.insert(0, start | 1);
+                    one_odd.sort();
+                    assert!(!x.contains_all(&one_odd));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn find_gte_from_hint_sorted_queries() {
+        for n in 0..sizes(64) {
+            let x = OrderedCollection::from((0..n).map(|i| 2 * i).collect::<Vec<_>>());
+            for step in 1..4 {
+                let mut hint = 0;
+                for q in (0..2 * n + 2).step_by(step) {
+                    let (next, v) = x.find_gte_from_hint(q, hint);
+                    assert_eq!(v, x.find_gte(q));
+                    hint = next;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn find_gte_from_hint_duplicates() {
+        let x = OrderedCollection::from(vec![1, 1, 2, 2, 2, 2, 3, 5, 5, 5]);
+        let mut hint = 0;
+        for q in 0..7 {
+            let (next, v) = x.find_gte_from_hint(q, hint);
+            assert_eq!(v, x.find_gte(q));
+            hint = next;
+        }
+    }
+
+    #[test]
+    fn depth() {
+        assert_eq!(OrderedCollection::<u32>::from(vec![]).depth(), 0);
+        for shift in 0..12 {
+            let n = 1usize << shift;
+            let x = OrderedCollection::from((0..n).collect::<Vec<_>>());
+            assert_eq!(x.depth(), shift + 1);
+            let x = OrderedCollection::from((0..n - 1).collect::<Vec<_>>());
+            assert_eq!(x.depth(), shift);
+            let x = OrderedCollection::from((0..n + 1).collect::<Vec<_>>());
+            assert_eq!(x.depth(), if n == 1 { 2 } else { shift + 1 });
+        }
+    }
+
+    #[test]
+    fn from_slice_owned() {
+        let vals = [64, 1, 32, 2, 16, 4, 8];
+        let x = OrderedCollection::from_slice_owned(&vals);
+        assert_eq!(vals, [64, 1, 32, 2, 16, 4, 8]);
+        assert_eq!(x.find_gte(0), Some(&1));
+        assert_eq!(x.find_gte(5), Some(&8));
+        assert_eq!(x.find_gte(64), Some(&64));
+        assert_eq!(x.find_gte(65), None);
+    }
+
+    #[test]
+    fn find_gte_deref() {
+        let mut vals = [64, 1, 32, 2, 16, 4, 8];
+        let found = {
+            let x = OrderedCollection::from_slice(&mut vals);
+            assert_eq!(x.find_gte_deref(0), Some(&1));
+            assert_eq!(x.find_gte_deref(65), None);
+            x.find_gte_deref(5)
+        };
+        // the result outlives the collection
+        assert_eq!(found, Some(&8));
+    }
+
+    #[test]
+    fn sorted_eytzinger_mapping() {
+        use super::{eytzinger_first, eytzinger_next, eytzinger_to_sorted, sorted_to_eytzinger};
+
+        for n in 1..sizes(300) {
+            let mut i = eytzinger_first(n);
+            for r in 0..n {
+                assert_eq!(eytzinger_to_sorted(i, n), r);
+                assert_eq!(sorted_to_eytzinger(r, n), i);
+                i = eytzinger_next(i, n);
+            }
+            assert_eq!(i, n);
+        }
+    }
+
+    #[test]
+    fn from_vec_in_place() {
+        for n in 0..sizes(300) {
+            let v: Vec<_> = (0..n).map(|i| (i * 37) % 101).collect();
+            let a = OrderedCollection::from(v.clone());
+            let b = OrderedCollection::from_vec_in_place(v);
+            assert_eq!(a.items, b.items);
+        }
+    }
+
+    #[test]
+    fn from_dedup() {
+        let x = OrderedCollection::from_dedup(vec![1, 1, 2, 2, 3]);
+        assert_eq!(x.len(), 3);
+        assert_eq!(x.find_gte(0), Some(&1));
+        assert_eq!(x.find_gte(1), Some(&1));
+        assert_eq!(x.find_gte(2), Some(&2));
+        assert_eq!(x.find_gte(3), Some(&3));
+        assert_eq!(x.find_gte(4), None);
+    }
+
+    #[test]
+    fn eytzinger_unpermute() {
+        for n in 0..sizes(300) {
+            let sorted: Vec<_> = (0..n).collect();
+            let mut v = sorted.clone();
+            super::eytzinger_permute(&mut v);
+            assert_eq!(v, OrderedCollection::from(sorted.clone()).items);
+            super::eytzinger_unpermute(&mut v);
+            assert_eq!(v, sorted);
+        }
+    }
+
+    #[test]
+    fn retain() {
+        for n in 0..sizes(100) {
+            let mut x = OrderedCollection::from((0..n).collect::<Vec<_>>());
+            x.retain(|&v| v % 2 == 0);
+            let evens = OrderedCollection::from((0..n).filter(|v| v % 2 == 0).collect::<Vec<_>>());
+            assert_eq!(x.items, evens.items);
+            for q in 0..n + 1 {
+                assert_eq!(x.find_gte(q), evens.find_gte(q));
+            }
+        }
+    }
+
+    #[test]
+    fn split_off() {
+        let mut a = OrderedCollection::from(vec![1, 2, 3, 4, 5]);
+        let b = a.split_off(3);
+        assert_eq!(a.items, OrderedCollection::from(vec![1, 2]).items);
+        assert_eq!(b.items, OrderedCollection::from(vec![3, 4, 5]).items);
+
+        let mut a = OrderedCollection::from(vec![1, 2, 3, 4, 5]);
+        let b = a.split_off(0);
+        assert!(a.is_empty());
+        assert_eq!(b.len(), 5);
+
+        let mut a = OrderedCollection::from(vec![1, 2, 3, 4, 5]);
+        let b = a.split_off(6);
+        assert_eq!(a.len(), 5);
+        assert!(b.is_empty());
+
+        let mut a = OrderedCollection::from(vec![1, 2, 2, 2, 3]);
+        let b = a.split_off(2);
+        assert_eq!(a.items, vec![1]);
+        assert_eq!(b.len(), 4);
+    }
+
+    #[test]
+    fn hash_ignores_construction_order() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashMap;
+        use std::hash::{Hash, Hasher};
+
+        fn hash<T: Hash>(t: &T) -> u64 {
+            let mut h = DefaultHasher::new();
+            t.hash(&mut h);
+            h.finish()
+        }
+
+        let a = OrderedCollection::from(vec![3, 1, 2]);
+        let b = OrderedCollection::from(vec![1, 2, 3]);
+        assert!(a == b);
+        assert_eq!(hash(&a), hash(&b));
+        assert!(a != OrderedCollection::from(vec![1, 2, 3, 3]));
+
+        let mut m = HashMap::new();
+        m.insert(a, "a");
+        assert_eq!(m.get(&b), Some(&"a"));
+    }
+
+    #[test]
+    fn find_all_eq() {
+        let x = OrderedCollection::from(vec![2, 2, 2, 5]);
+        let all: Vec<_> = x.find_all_eq(2).collect();
+        assert_eq!(all, vec![&2, &2, &2]);
+        let all: Vec<_> = x.find_all_eq(5).collect();
+        assert_eq!(all, vec![&5]);
+        assert_eq!(x.find_all_eq(1).next(), None);
+        assert_eq!(x.find_all_eq(3).next(), None);
+        assert_eq!(x.find_all_eq(6).next(), None);
+
+        let x = OrderedCollection::from(vec![1, 1, 2, 3, 3, 3, 3, 4, 4, 9, 9, 9]);
+        for q in 0..11 {
+            let n = x.items.iter().filter(|&&v| v == q).count();
+            assert!(x.find_all_eq(q).all(|&v| v == q));
+            assert_eq!(x.find_all_eq(q).count(), n);
+        }
+    }
+
+    #[test]
+    fn count() {
+        let x = OrderedCollection::from(vec![1, 1, 2, 3, 3, 3, 3, 4, 4, 9, 9, 9]);
+        assert_eq!(x.count(0), 0);
+        assert_eq!(x.count(1), 2);
+        assert_eq!(x.count(2), 1);
+        assert_eq!(x.count(3), 4);
+        assert_eq!(x.count(4), 2);
+        assert_eq!(x.count(5), 0);
+        assert_eq!(x.count(9), 3);
+        assert_eq!(x.count(10), 0);
+
+        for n in 0..sizes(100) {
+            let v: Vec<_> = (0..n).map(|i| i / 3).collect();
+            let x = OrderedCollection::from(v.clone());
+            for q in 0..n / 3 + 2 {
+                assert_eq!(x.count(q), v.iter().filter(|&&e| e == q).count());
+            }
+        }
+    }
+
+    #[test]
+    fn f64_normal_values() {
+        let x = OrderedCollection::from_f64(vec![2.5, -1.0, 1e10, -3.75, 0.5]);
+        assert_eq!(x.find_gte_f64(-10.0), Some(&-3.75));
+        assert_eq!(x.find_gte_f64(-3.75), Some(&-3.75));
+        assert_eq!(x.find_gte_f64(-2.0), Some(&-1.0));
+        assert_eq!(x.find_gte_f64(0.0), Some(&0.5));
+        assert_eq!(x.find_gte_f64(1.0), Some(&2.5));
+        assert_eq!(x.find_gte_f64(1e9), Some(&1e10));
+        assert_eq!(x.find_gte_f64(f64::INFINITY), None);
+    }
+
+    #[test]
+    fn f64_signed_zero() {
+        let x = OrderedCollection::from_f64(vec![0.0, -0.0]);
+        let v = x.find_gte_f64(-0.0).unwrap();
+        assert!(v.is_sign_negative());
+        let v = x.find_gte_f64(0.0).unwrap();
+        assert!(v.is_sign_positive());
+        let v = x.find_gte_f64(-1.0).unwrap();
+        assert!(v.is_sign_negative());
+        assert!(OrderedF64(-0.0) < OrderedF64(0.0));
+    }
+
+    #[test]
+    fn f64_nan() {
+        let x = OrderedCollection::from_f64(vec![f64::NAN, 1.0, f64::INFINITY, -f64::NAN]);
+        // a NaN with the sign bit set sorts at the low end
+        assert!(x.find_gte_f64(-f64::NAN).unwrap().is_nan());
+        assert_eq!(x.find_gte_f64(f64::NEG_INFINITY), Some(&1.0));
+        assert_eq!(x.find_gte_f64(2.0), Some(&f64::INFINITY));
+        assert_eq!(x.find_gte_f64(f64::INFINITY), Some(&f64::INFINITY));
+        // but a regular NaN sorts at the high end
+        assert!(x.find_gte_f64(f64::NAN).unwrap().is_nan());
+        let y = OrderedCollection::from_f64(vec![f64::NAN, 1.0]);
+        assert!(y.find_gte_f64(f64::INFINITY).unwrap().is_nan());
+    }
+
+    #[test]
+    fn construction_owned_elements() {
+        // heap-allocated elements let miri catch reads of uninitialized slots, double drops, and
+        // leaks in the construction paths, for every shape of the last level of the tree.
+        for n in 0..sizes(70) {
+            let v: Vec<_> = (0..n).map(|i| format!("{:03}", (i * 37) % 101)).collect();
+            let a = OrderedCollection::from(v.clone());
+            let b = OrderedCollection::from_vec_in_place(v.clone());
+            assert!(a == b);
+
+            for s in &v {
+                assert_eq!(a.find_gte(s.clone()), Some(s));
+            }
+
+            let mut c = OrderedCollection::from_slice_owned(&v);
+            c.retain(|s| s.ends_with('1'));
+            let d = c.split_off(String::from("050"));
+            assert_eq!(
+                c.len() + d.len(),
+                v.iter().filter(|s| s.ends_with('1')).count()
+            );
+        }
+    }
+
+    /// An iterator that reports a length of `claimed`, regardless of how many elements it yields.
+    struct Liar<I> {
+        inner: I,
+        claimed: usize,
+    }
+
+    impl<I: Iterator> Iterator for Liar<I> {
+        type Item = I::Item;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.inner.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.claimed, Some(self.claimed))
+        }
+    }
+
+    impl<I: Iterator> ExactSizeIterator for Liar<I> {}
+
+    #[test]
+    fn from_sorted_iter_overreported_len() {
+        use std::panic;
+        use std::rc::Rc;
+
+        for n in 0..sizes(20) {
+            for claimed in n + 1..sizes(40) {
+                let e = Rc::new(());
+                let v = vec![e.clone(); n];
+                let r = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                    OrderedCollection::from_sorted_iter(Liar {
+                        inner: v.into_iter(),
+                        claimed,
+                    })
+                }));
+                assert!(r.is_err());
+                // every element taken from the iterator must have been dropped
+                assert_eq!(Rc::strong_count(&e), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn from_sorted_iter_underreported_len() {
+        let x = OrderedCollection::from_sorted_iter(Liar {
+            inner: 0..10,
+            claimed: 5,
+        });
+        assert_eq!(x.len(), 5);
+        assert_eq!(x.find_gte(3), Some(&3));
+        assert_eq!(x.find_gte(5), None);
+    }
+
+    #[test]
+    fn zero_sized() {
+        let x = OrderedCollection::from(vec![(), (), ()]);
+        assert_eq!(x.len(), 3);
+        assert_eq!(x.find_gte(()), Some(&()));
+    }
+
+    #[test]
+    fn cursor_gte() {
+        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64, 128, 256]);
+        let mut c = x.cursor_gte(3);
+        assert_eq!(c.current(), Some(&4));
+        assert_eq!(c.next(), Some(&4));
+        assert_eq!(c.current(), Some(&8));
+        let rest: Vec<_> = c.cloned().collect();
+        assert_eq!(rest, vec![8, 16, 32, 64, 128, 256]);
+
+        let mut c = x.cursor_gte(256);
+        assert_eq!(c.current(), Some(&256));
+        assert_eq!(c.next(), Some(&256));
+        assert_eq!(c.current(), None);
+        assert_eq!(c.next(), None);
+
+        let mut c = x.cursor_gte(257);
+        assert_eq!(c.current(), None);
+        assert_eq!(c.next(), None);
+
+        assert_eq!(x.cursor_gte(0).count(), 9);
+    }
+
+    #[test]
+    #[cfg(feature = "nightly")]
+    fn find_gte_many() {
+        for n in 0..sizes(100) {
+            let x = OrderedCollection::from((0..n as u32).map(|i| 2 * i).collect::<Vec<_>>());
+            for len in 0..20 {
+                let xs: Vec<_> = (0..len).map(|i| (i * 7) % (2 * n as u32 + 3)).collect();
+                let expected: Vec<_> = xs.iter().map(|&q| x.find_gte(q)).collect();
+                assert_eq!(x.find_gte_many(&xs), expected);
+            }
+        }
+
+        let x = OrderedCollection::from(vec![1i64, 1, 2, 2, 2, 3, 5, 5, 9, 9, 9]);
+        let xs: Vec<_> = (-2..12).collect();
+        let expected: Vec<_> = xs.iter().map(|&q| x.find_gte(q)).collect();
+        assert_eq!(x.find_gte_many(&xs), expected);
+    }
+
+    #[test]
+    fn dyn_comparators() {
+        let v = vec![16, 4, 64, 1, 32, 2, 8, 128, 256];
+        let asc = OrderedCollectionDyn::new(v.clone(), |a: &i32, b: &i32| a.cmp(b));
+        let desc = OrderedCollectionDyn::new(v, |a: &i32, b: &i32| b.cmp(a));
+        assert_eq!(asc.len(), 9);
+        assert_eq!(desc.len(), 9);
+
+        assert_eq!(asc.find_gte(&0), Some(&1));
+        assert_eq!(asc.find_gte(&5), Some(&8));
+        assert_eq!(asc.find_gte(&256), Some(&256));
+        assert_eq!(asc.find_gte(&257), None);
+
+        // in descending order, "greater or equal" means "no larger than"
+        assert_eq!(desc.find_gte(&0), None);
+        assert_eq!(desc.find_gte(&5), Some(&4));
+        assert_eq!(desc.find_gte(&1), Some(&1));
+        assert_eq!(desc.find_gte(&1000), Some(&256));
+
+        let plain = OrderedCollection::from(vec![16, 4, 64, 1, 32, 2, 8, 128, 256]);
+        for q in 0..300 {
+            assert_eq!(asc.find_gte(&q), plain.find_gte(q));
+        }
+    }
+
+    #[test]
+    fn find_gt() {
+        for n in 0..sizes(100) {
+            let v: Vec<_> = (0..n).map(|i| i / 2).collect();
+            let x = OrderedCollection::from(v.clone());
+            for q in 0..n / 2 + 2 {
+                assert_eq!(x.find_gt(q), v.iter().find(|&&e| e > q));
+                assert_eq!(x.upper_bound(q), x.find_gt(q));
+                assert_eq!(x.lower_bound(q), x.find_gte(q));
+            }
+        }
+    }
+
+    #[test]
+    fn mask() {
+        for n in 0..sizes(300) {
+            let x = OrderedCollection::from((0..n).collect::<Vec<_>>());
+            assert_eq!(x.mask, (n + 1).next_power_of_two() - 1);
+        }
+    }
+
+    #[test]
+    fn mask_large_n() {
+        use super::eytzinger_mask;
+
+        assert_eq!(eytzinger_mask(0), 0);
+        assert_eq!(eytzinger_mask(1), 1);
+        assert_eq!(eytzinger_mask(2), 3);
+        let top = 1usize << (usize::BITS - 1);
+        assert_eq!(eytzinger_mask(top - 1), top - 1);
+        assert_eq!(eytzinger_mask(top), usize::MAX);
+        assert_eq!(eytzinger_mask(top + 1), usize::MAX);
+        assert_eq!(eytzinger_mask(usize::MAX - 1), usize::MAX);
+        assert_eq!(eytzinger_mask(usize::MAX), usize::MAX);
+    }
+
+    #[test]
+    fn from_sorted_slice() {
+        let vals = [1, 2, 4, 8, 16, 32, 64, 128, 256];
+        let x = OrderedCollection::from_sorted_slice(&vals);
+        assert_eq!(x.len(), vals.len());
+        for i in 0..257 {
+            assert_eq!(x.find_gte_deref(i), vals.iter().find(|&&v| v >= i));
+        }
+        assert_eq!(x.find_gte_deref(257), None);
+    }
+
+    #[test]
+    #[cfg(feature = "nightly")]
+    fn calibrate_prefetch() {
+        for &n in &[0, 1, 7, 1000] {
+            let mut x = OrderedCollection::from((0..n).map(|i| 2 * i).collect::<Vec<_>>());
+            x.calibrate_prefetch();
+            for q in 0..2 * n + 1 {
+                assert_eq!(
+                    x.find_gte(q),
+                    (0..n).map(|i| 2 * i).find(|&v| v >= q).as_ref()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn from_sorted_iter_with_len() {
+        let exact = OrderedCollection::from_sorted_iter_with_len((0..10).filter(|_| true), 10);
+        assert!(exact == OrderedCollection::from((0..10).collect::<Vec<_>>()));
+
+        let over = OrderedCollection::from_sorted_iter_with_len((0..20).filter(|_| true), 10);
+        assert!(over == exact);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_sorted_iter_with_len_short() {
+        let _ = OrderedCollection::from_sorted_iter_with_len((0..5).filter(|_| true), 10);
+    }
+
+    #[test]
+    fn min_max() {
+        for n in 0..sizes(300) {
+            let x = OrderedCollection::from((0..n).map(|i| (i * 37) % 101).collect::<Vec<_>>());
+            assert_eq!(x.min(), x.items.iter().min());
+            assert_eq!(x.max(), x.items.iter().max());
+        }
+    }
+
+    #[test]
+    fn nth_from_end() {
+        for n in 0..sizes(300) {
+            let v: Vec<_> = (0..n).map(|i| (i * 37) % 101).collect();
+            let x = OrderedCollection::from(v.clone());
+            let mut sorted = v;
+            sorted.sort_unstable();
+            assert_eq!(x.nth_from_end(0), x.max());
+            for k in 0..n + 2 {
+                assert_eq!(
+                    x.nth_from_end(k),
+                    sorted.iter().rev().nth(k),
+                    "n = {}, k = {}",
+                    n,
+                    k
+                );
+            }
+        }
+        assert_eq!(OrderedCollection::<u32>::from(vec![]).nth_from_end(0), None);
+    }
+
+    #[test]
+    fn sorted_rejects_unsorted() {
+        assert_eq!(Sorted::new(vec![2, 1]), Err(vec![2, 1]));
+        assert_eq!(Sorted::new(vec![1, 2, 4, 3]), Err(vec![1, 2, 4, 3]));
+        assert!(Sorted::new(Vec::<u32>::new()).is_ok());
+        assert!(Sorted::new(vec![1]).is_ok());
+        assert!(Sorted::new(vec![1, 1, 2, 2]).is_ok());
+        assert_eq!(Sorted::sort(vec![2, 1]).into_inner(), vec![1, 2]);
+    }
+
+    #[test]
+    fn from_sorted() {
+        let v = vec![1, 2, 4, 8, 16, 32, 64, 128, 256];
+        let a = OrderedCollection::from(Sorted::new(v.clone()).unwrap());
+        assert!(a == OrderedCollection::from(v));
+        let b = OrderedCollection::from(Sorted::sort(vec![256, 1, 128, 2, 64, 4, 32, 8, 16]));
+        assert!(a == b);
+    }
+
+    #[test]
+    fn extend_from_sorted() {
+        for n in 0..sizes(40) {
+            for m in 0..sizes(40) {
+                let a: Vec<_> = (0..n).map(|i| 3 * i).collect();
+                let b: Vec<_> = (0..m).map(|i| 2 * i).collect();
+                let mut x = OrderedCollection::from(a.clone());
+                x.extend_from_sorted(b.clone());
+                let mut all = a;
+                all.extend(b);
+                assert!(x == OrderedCollection::from(all));
+            }
+        }
+    }
+
+    #[test]
+    fn from_with_stats() {
+        let (x, stats) = OrderedCollection::from_with_stats(vec![5, 3, 1, 4, 2]);
+        assert!(x == OrderedCollection::from(vec![1, 2, 3, 4, 5]));
+        assert_eq!(
+            stats,
+            BuildStats {
+                len: 5,
+                needed_sort: true,
+                writes: 5,
+            }
+        );
+
+        let (x, stats) = OrderedCollection::from_with_stats(vec![1, 1, 2, 3]);
+        assert_eq!(x.len(), 4);
+        assert!(!stats.needed_sort);
+        assert_eq!(stats.writes, 4);
+
+        let (x, stats) = OrderedCollection::<u8>::from_with_stats(vec![]);
+        assert!(x.is_empty());
+        assert_eq!(
+            stats,
+            BuildStats {
+                len: 0,
+                needed_sort: false,
+                writes: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn cow() {
+        use std::borrow::Cow;
+
+        let owned = [3, 9];
+        let borrowed = [1, 5, 7];
+        let mut v: Vec<Cow<'_, i32>> = borrowed.iter().map(Cow::Borrowed).collect();
+        v.extend(owned.iter().map(|&e| Cow::Owned(e)));
+        let x = OrderedCollection::from(v);
+        assert_eq!(x.find_gte(0).map(|e| **e), Some(1));
+        assert_eq!(x.find_gte(2).map(|e| **e), Some(3));
+        assert_eq!(x.find_gte(6).map(|e| **e), Some(7));
+        assert_eq!(x.find_gte(8).map(|e| **e), Some(9));
+        assert_eq!(x.find_gte(10), None);
+
+        let b = String::from("b");
+        let x = OrderedCollection::from(vec![
+            Cow::Owned(String::from("c")),
+            Cow::Borrowed("a"),
+            Cow::Borrowed(&*b),
+        ]);
+        assert_eq!(x.find_gte(Cow::Borrowed("bb")).map(|e| &**e), Some("c"));
+        assert_eq!(
+            x.find_gte(Cow::Owned(String::from("b"))).map(|e| &**e),
+            Some("b")
+        );
+    }
+
+    #[test]
+    fn find_gte_in_rank_range() {
+        let x = OrderedCollection::from((0..10).map(|i| 10 * i).collect::<Vec<_>>());
+        // window entirely below the match
+        assert_eq!(x.find_gte_in_rank_range(55, 0, 4), None);
+        // window entirely above the match
+        assert_eq!(x.find_gte_in_rank_range(55, 8, 10), Some(&80));
+        // window straddling the match
+        assert_eq!(x.find_gte_in_rank_range(55, 3, 8), Some(&60));
+        // no match at all
+        assert_eq!(x.find_gte_in_rank_range(95, 0, 10), None);
+        // empty and out-of-range windows
+        assert_eq!(x.find_gte_in_rank_range(0, 5, 5), None);
+        assert_eq!(x.find_gte_in_rank_range(0, 7, 3), None);
+        assert_eq!(x.find_gte_in_rank_range(0, 12, 20), None);
+        assert_eq!(x.find_gte_in_rank_range(0, 9, 20), Some(&90));
+    }
+
+    #[test]
+    fn find_gte_in_rank_range_exhaustive() {
+        for n in 0..sizes(20) {
+            let v: Vec<_> = (0..n).map(|i| 2 * i).collect();
+            let x = OrderedCollection::from(v.clone());
+            for q in 0..2 * n + 1 {
+                for lo in 0..n + 1 {
+                    for hi in 0..n + 2 {
+                        let expected = v[lo..hi.clamp(lo, n)].iter().find(|&&e| e >= q);
+                        assert_eq!(x.find_gte_in_rank_range(q, lo, hi), expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn index() {
+        for n in 0..sizes(100) {
+            let v: Vec<_> = (0..n).map(|i| 3 * i).collect();
+            let x = OrderedCollection::from(v.clone());
+            for (r, e) in v.iter().enumerate() {
+                assert_eq!(&x[r], e);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn index_out_of_bounds() {
+        let x = OrderedCollection::from(vec![1, 2, 3]);
+        let _ = x[3];
+    }
+
+    #[test]
+    fn builder() {
+        let unsorted = vec![5, 3, 9, 3, 1, 9, 7];
+        let sorted = vec![1, 3, 3, 5, 7, 9, 9];
+        for &dedup in &[false, true] {
+            for &presorted in &[false, true] {
+                for &offset in &[None, Some(0), Some(3)] {
+                    let mut b = OrderedCollection::builder()
+                        .dedup(dedup)
+                        .presorted(presorted);
+                    if let Some(offset) = offset {
+                        b = b.prefetch_offset(offset);
+                    }
+                    let v = if presorted {
+                        sorted.clone()
+                    } else {
+                        unsorted.clone()
+                    };
+                    let x = b.build(v);
+                    let expected = if dedup {
+                        OrderedCollection::from_dedup(unsorted.clone())
+                    } else {
+                        OrderedCollection::from(unsorted.clone())
+                    };
+                    assert!(x == expected);
+                    assert_eq!(x.offset, offset.unwrap_or(expected.offset));
+                    for q in 0..11 {
+                        assert_eq!(x.find_gte(q), expected.find_gte(q));
+                    }
+                }
+            }
+        }
+    }
+
+    // run with and without the `safe` feature to check that both builds give the same results
+    #[test]
+    fn matches_partition_point() {
+        for n in 0..sizes(200) {
+            let v: Vec<_> = (0..n).map(|i| 2 * i).collect();
+            let x = OrderedCollection::from(v.clone());
+            for q in 0..2 * n + 1 {
+                let gte = v.partition_point(|&e| e < q);
+                let gt = v.partition_point(|&e| e <= q);
+                assert_eq!(x.find_gte(q), v.get(gte));
+                assert_eq!(x.find_gt(q), v.get(gt));
+                assert_eq!(x.find_gte_from_hint(q, 0).1, v.get(gte));
+                assert_eq!(x.find_gte_in_rank_range(q, 0, n), v.get(gte));
+            }
+        }
+    }
+
+    #[test]
+    fn string_and_str_agree() {
+        let words = vec![
+            "pear", "apple", "fig", "Banana", "apricot", "", "peach", "ápple",
+        ];
+        let strings =
+            OrderedCollection::from(words.iter().map(|&w| String::from(w)).collect::<Vec<_>>());
+        let strs = OrderedCollection::from(words.clone());
+        for q in &[
+            "", "a", "apq", "b", "Banana", "fig", "figs", "pea", "zebra", "á", "ápple",
+        ] {
+            let expected = words.iter().filter(|w| *w >= q).min();
+            assert_eq!(strs.find_gte(*q), expected);
+            assert_eq!(
+                strings.find_gte(String::from(*q)).map(|s| s.as_str()),
+                expected.cloned()
+            );
+        }
+    }
+
+    #[test]
+    fn try_from_sorted_iter() {
+        for n in 0..sizes(100) {
+            let x = OrderedCollection::try_from_sorted_iter(0..n).unwrap();
+            assert!(x == OrderedCollection::from_sorted_iter(0..n));
+        }
+
+        // claim more elements than can possibly be allocated
+        let liar = Liar {
+            inner: 0u64..3,
+            claimed: usize::MAX,
+        };
+        assert!(OrderedCollection::try_from_sorted_iter(liar).is_err());
+    }
+
+    #[test]
+    fn no_spare_capacity() {
+        let v: Vec<_> = (0..1000).map(|i| i % 10).collect();
+
+        let x = OrderedCollection::from_dedup(v.clone());
+        assert_eq!(x.len(), 10);
+        assert_eq!(x.items.capacity(), x.len());
+
+        let mut x = OrderedCollection::from(v.clone());
+        x.retain(|&e| e == 3);
+        assert_eq!(x.len(), 100);
+        assert_eq!(x.items.capacity(), x.len());
+
+        let x = OrderedCollection::builder().dedup(true).build(v.clone());
+        assert_eq!(x.items.capacity(), x.len());
+
+        let mut w = Vec::with_capacity(2000);
+        w.extend(v);
+        let x = OrderedCollection::from_vec_in_place(w);
+        assert_eq!(x.items.capacity(), x.len());
+    }
+
+    #[test]
+    fn ord() {
+        use std::cmp::Ordering;
+
+        let c = |v: Vec<u32>| OrderedCollection::from(v);
+        assert!(c(vec![1, 2]) < c(vec![1, 3]));
+        assert!(c(vec![2, 1]) < c(vec![3, 1]));
+        assert!(c(vec![1, 2]) < c(vec![1, 2, 3]));
+        assert!(c(vec![]) < c(vec![0]));
+        assert!(c(vec![1, 2, 3]) > c(vec![1, 2]));
+        assert!(c(vec![3]) > c(vec![1, 2, 3]));
+        assert_eq!(
+            c(vec![2, 1]).partial_cmp(&c(vec![1, 2])),
+            Some(Ordering::Equal)
+        );
+
+        // compare against the sorted vectors for every pair of small collections
+        let vs: Vec<Vec<u32>> = (0..sizes(64))
+            .map(|bits| (0..6).filter(|i| bits & (1 << i) != 0).collect())
+            .collect();
+        for a in &vs {
+            for b in &vs {
+                assert_eq!(c(a.clone()).partial_cmp(&c(b.clone())), a.partial_cmp(b));
+            }
+        }
+
+        let x = OrderedCollection::from_f64(vec![1.0, 2.0]);
+        assert!(x < OrderedCollection::from_f64(vec![1.0, 3.0]));
+    }
+
+    #[test]
+    fn find_gte_cmp() {
+        use std::cmp::Ordering;
+
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        let by_x = |p: &Point, x: &i32| p.x.cmp(x);
+        let points: Vec<_> = (0..sizes(50))
+            .map(|i| Point {
+                x: 3 * i as i32,
+                y: 7 - i as i32,
+            })
+            .collect();
+        let expected: Vec<_> = points.iter().map(|p| (p.x, p.y)).collect();
+        let c = OrderedCollection::from(points);
+        for q in -1..3 * sizes(50) as i32 + 1 {
+            let found = c.find_gte_cmp(&q, by_x).map(|p| (p.x, p.y));
+            assert_eq!(found, expected.iter().find(|p| p.0 >= q).cloned());
+        }
+
+        // a comparator that considers every element greater finds the minimum
+        assert_eq!(
+            c.find_gte_cmp(&(), |_, _| Ordering::Greater),
+            c.find_gte(Point { x: -1, y: 0 })
+        );
+        assert_eq!(c.find_gte_cmp(&(), |_, _| Ordering::Less), None);
+    }
+
+    #[test]
+    fn sorted_windows() {
+        let x = OrderedCollection::from(vec![1, 4, 9]);
+        let pairs: Vec<_> = x.sorted_windows().collect();
+        assert_eq!(pairs, [(&1, &4), (&4, &9)]);
+
+        for n in 0..sizes(40) {
+            let v: Vec<_> = (0..n).map(|i| i * i).collect();
+            let x = OrderedCollection::from(v.clone());
+            let pairs: Vec<_> = x.sorted_windows().collect();
+            let expected: Vec<_> = v.windows(2).map(|w| (&w[0], &w[1])).collect();
+            assert_eq!(pairs, expected);
+        }
+    }
+
+    #[test]
+    fn to_eytzinger_vec() {
+        for n in 0..sizes(100) {
+            let x = OrderedCollection::from((0..n).map(|i| 2 * i).collect::<Vec<_>>());
+            let v = x.to_eytzinger_vec();
+            assert_eq!(v.len(), x.len());
+
+            let y = OrderedCollection::from_eytzinger_vec(v);
+            assert_eq!(y.mask, x.mask);
+            for q in 0..2 * n + 1 {
+                assert_eq!(y.find_gte(q), x.find_gte(q));
+            }
+        }
+    }
+
+    #[test]
+    fn from_eytzinger_vec() {
+        for n in 0..sizes(100) {
+            let x = OrderedCollection::from((0..n).map(|i| n - i).collect::<Vec<_>>());
+            let y = OrderedCollection::from_eytzinger_vec(x.to_eytzinger_vec());
+            assert!(x == y);
+            assert_eq!(y.mask, x.mask);
+            assert_eq!(y.offset, x.offset);
+            for q in 0..n + 2 {
+                assert_eq!(y.find_gte(q), x.find_gte(q));
+            }
+        }
+    }
+
+    #[test]
+    fn find_gte_or_pos() {
+        for n in 0..sizes(100) {
+            let v: Vec<_> = (0..n).map(|i| 2 * (i / 2)).collect();
+            let x = OrderedCollection::from(v.clone());
+            for q in 0..n + 2 {
+                let expected = v
+                    .binary_search(&q)
+                    .map(|_| &v[v.partition_point(|&e| e < q)]);
+                assert_eq!(x.find_gte_or_pos(q), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn huge_zero_sized() {
+        // zero-sized elements take up no memory, so this is the only way to get a collection
+        // whose prefetch addresses reach the limits of pointer arithmetic
+        let n = isize::MAX as usize;
+        let mut v: Vec<()> = Vec::with_capacity(n);
+        // safe because `()` needs no initialization
+        unsafe { v.set_len(n) };
+        let x = OrderedCollection::from_eytzinger_vec(v);
+        assert_eq!(x.len(), n);
+        assert_eq!(x.find_gte(()), Some(&()));
+        assert_eq!(x.find_gt(()), None);
+    }
+
+    #[test]
+    fn prefetch_toggle() {
+        for n in 0..sizes(100) {
+            let mut x = OrderedCollection::from((0..n).map(|i| 2 * i).collect::<Vec<_>>());
+            assert!(x.prefetch_enabled());
+            let with: Vec<_> = (0..2 * n + 1).map(|q| x.find_gte(q).cloned()).collect();
+            x.set_prefetch_enabled(false);
+            assert!(!x.prefetch_enabled());
+            let without: Vec<_> = (0..2 * n + 1).map(|q| x.find_gte(q).cloned()).collect();
+            assert_eq!(with, without);
+        }
+    }
+
+    #[test]
+    fn sorted_cache() {
+        for n in 0..sizes(100) {
+            let v: Vec<_> = (0..n).map(|i| (7 * i) % 50).collect();
+            let x = CachedOrderedCollection::from(OrderedCollection::from(v.clone()));
+            let mut sorted = v;
+            sorted.sort();
+            assert_eq!(x.cached_len(), 0);
+            let first = x.sorted_cache().as_ptr();
+            assert_eq!(x.sorted_cache(), &sorted[..]);
+            assert_eq!(x.sorted_cache().as_ptr(), first);
+            assert!(x.sorted_cache().iter().eq(x.iter()));
+            assert_eq!(x.cached_len(), n);
+
+            // mutating the collection means taking it out, which drops the cache
+            let mut y = x.into_inner();
+            y.retain(|&e| e % 2 == 0);
+            sorted.retain(|&e| e % 2 == 0);
+            assert_eq!(CachedOrderedCollection::new(y).sorted_cache(), &sorted[..]);
+        }
+    }
+
+    #[test]
+    fn rebuilder() {
+        let mut r = Rebuilder::new();
+        let mut x = r.rebuild_from_sorted(0..sizes(100));
+        let first = x.items.as_ptr();
+        for round in 0..sizes(50) {
+            // vary the length, but never beyond the first allocation
+            let n = sizes(100) - round % 7;
+            let v: Vec<_> = (0..n).map(|i| 3 * i + round).collect();
+            let old = std::mem::replace(&mut x, r.rebuild_from_sorted(v.clone()));
+            r.recycle(old);
+            assert!(x == OrderedCollection::from(v.clone()));
+            for q in 0..3 * n + round + 1 {
+                assert_eq!(x.find_gte(q), v.iter().find(|&&e| e >= q));
+            }
+        }
+
+        // the two allocations are handed back and forth
+        let old = std::mem::replace(&mut x, r.rebuild_from_sorted(0..1));
+        r.recycle(old);
+        let y = r.rebuild_from_sorted(0..1);
+        assert!(x.items.as_ptr() == first || y.items.as_ptr() == first);
+    }
+
+    #[test]
+    fn find_gte_detailed() {
+        let x = OrderedCollection::from(vec![10, 20, 20, 30]);
+        assert_eq!(x.find_gte_detailed(5), SearchResult::BelowMin(&10));
+        assert_eq!(x.find_gte_detailed(10), SearchResult::Exact(&10));
+        assert_eq!(x.find_gte_detailed(15), SearchResult::Between(&20));
+        assert_eq!(x.find_gte_detailed(20), SearchResult::Exact(&20));
+        assert_eq!(x.find_gte_detailed(30), SearchResult::Exact(&30));
+        assert_eq!(x.find_gte_detailed(31), SearchResult::AboveMax);
+
+        let empty = OrderedCollection::<u32>::from(vec![]);
+        assert_eq!(empty.find_gte_detailed(0), SearchResult::AboveMax);
+
+        for n in 1..sizes(50) {
+            let v: Vec<_> = (0..n).map(|i| 2 * i + 1).collect();
+            let x = OrderedCollection::from(v.clone());
+            for q in 0..2 * n + 2 {
+                let expected = match v.binary_search(&q) {
+                    Ok(i) => SearchResult::Exact(&v[i]),
+                    Err(0) => SearchResult::BelowMin(&v[0]),
+                    Err(i) if i == n => SearchResult::AboveMax,
+                    Err(i) => SearchResult::Between(&v[i]),
+                };
+                assert_eq!(x.find_gte_detailed(q), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn ordered_map_from_btreemap() {
+        use std::collections::BTreeMap;
+
+        for n in 0..sizes(50) {
+            let m: BTreeMap<_, _> = (0..n).map(|i| (format!("{:03}", 2 * i), i)).collect();
+            let x = OrderedMap::from(m.clone());
+            assert_eq!(x.len(), n);
+            assert_eq!(x.is_empty(), n == 0);
+            for q in 0..2 * n + 1 {
+                let q = format!("{:03}", q);
+                assert_eq!(x.get(q.as_str()), m.get(q.as_str()));
+                assert_eq!(x.find_gte(q.as_str()), m.range(q.clone()..).next());
+            }
+        }
+    }
+
+    #[test]
+    fn ordered_map_entries() {
+        use std::collections::BTreeMap;
+
+        for n in 0..sizes(50) {
+            let m: BTreeMap<_, _> = (0..n).map(|i| (2 * i, vec![i])).collect();
+            let mut x = OrderedMap::from(m.clone());
+            for q in 0..2 * n + 1 {
+                let e: Option<Entry<_, _>> = x.get_gte_entry(&q);
+                assert_eq!(e.map(|e| (e.key(), e.value())), m.range(q..).next());
+            }
+
+            // modify every value through an entry, and check that the changes stick
+            for q in 0..2 * n + 1 {
+                let mut e = match x.get_gte_entry_mut(&q) {
+                    Some(e) => e,
+                    None => {
+                        assert!(m.range(q..).next().is_none());
+                        continue;
+                    }
+                };
+                let k = *e.key();
+                assert_eq!(e.value()[0], m[&k][0]);
+                e.value_mut().push(q);
+            }
+            for (k, v) in &m {
+                // each key is found by the query equal to it, and by the one just below it
+                let mut expect = v.clone();
+                if *k > 0 {
+                    expect.push(k - 1);
+                }
+                expect.push(*k);
+                assert_eq!(x.get(k), Some(&expect));
+                assert_eq!(x.get_gte_entry(k).map(|e| e.key()), Some(k));
+            }
+            if let Some(e) = x.get_gte_entry_mut(&0) {
+                e.into_value_mut().clear();
+                assert_eq!(x.get(&0), Some(&vec![]));
+            }
+        }
+    }
+
+    #[test]
+    fn find_gte_interpolating() {
+        let layouts: [fn(u32) -> u32; 4] = [
+            |i| 2 * i,
+            |i| i / 3,
+            |i| i * i,
+            |i| if i % 2 == 0 { i } else { 1 << 20 | i },
+        ];
+        for f in &layouts {
+            for n in 0..sizes(300) as u32 {
+                let v: Vec<u32> = {
+                    let mut v: Vec<_> = (0..n).map(f).collect();
+                    v.sort();
+                    v
+                };
+                let x = OrderedCollection::from(v.clone());
+                let max = v.last().map_or(0, |&m| m + 1);
+                for q in (0..max + 2).step_by(1 + max as usize / 500) {
+                    assert_eq!(x.find_gte_interpolating(q), x.find_gte(q));
+                }
+                for &e in &v {
+                    assert_eq!(x.find_gte_interpolating(e), x.find_gte(e));
+                    if e > 0 {
+                        assert_eq!(x.find_gte_interpolating(e - 1), x.find_gte(e - 1));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn eq_as_set() {
+        let c = |v: Vec<u32>| OrderedCollection::from(v);
+        assert!(c(vec![1, 1, 2]).eq_as_set(&c(vec![1, 2, 2])));
+        assert!(c(vec![]).eq_as_set(&c(vec![])));
+        assert!(!c(vec![1]).eq_as_set(&c(vec![])));
+        assert!(!c(vec![1, 2]).eq_as_set(&c(vec![1, 2, 3])));
+        assert!(!c(vec![1, 2, 2]).eq_as_set(&c(vec![1, 1, 3])));
+
+        for a in 0..sizes(64) {
+            for b in 0..sizes(64) {
+                // the bits of a and b pick which of 0..3 occur, and how many times
+                let v = |bits: usize| -> Vec<u32> {
+                    (0..3)
+                        .flat_map(|i| std::iter::repeat(i).take((bits >> (2 * i)) & 3))
+                        .collect()
+                };
+                let set = |v: &[u32]| v.iter().cloned().collect::<std::collections::BTreeSet<_>>();
+                let (va, vb) = (v(a), v(b));
+                assert_eq!(
+                    c(va.clone()).eq_as_set(&c(vb.clone())),
+                    set(&va) == set(&vb)
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn from_sorted_stream() {
+        use futures::executor::block_on;
+        use futures::stream;
+
+        for n in 0..sizes(50) {
+            let s = stream::iter((0..n).map(|i| 2 * i));
+            let x = block_on(OrderedCollection::from_sorted_stream(s, n));
+            assert!(x == OrderedCollection::from_sorted_iter((0..n).map(|i| 2 * i)));
+
+            // elements past `len` are ignored
+            let s = stream::iter(0..2 * n);
+            let x = block_on(OrderedCollection::from_sorted_stream(s, n));
+            assert!(x == OrderedCollection::from_sorted_iter(0..n));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    #[should_panic(expected = "iterator yielded fewer elements than it reported")]
+    fn from_sorted_stream_too_short() {
+        let s = futures::stream::iter(0..3);
+        let _ = futures::executor::block_on(OrderedCollection::from_sorted_stream(s, 4));
+    }
+
+    #[test]
+    fn debug_validate() {
+        use super::sorted_to_eytzinger;
+
+        for n in 0..sizes(100) {
+            let mut x = OrderedCollection::from_sorted_iter(0..n);
+            assert!(x.debug_validate());
+
+            if n >= 2 {
+                // swap the first two elements in sorted order
+                let (a, b) = (sorted_to_eytzinger(0, n), sorted_to_eytzinger(1, n));
+                x.items.swap(a, b);
+                assert!(!x.debug_validate());
+                x.items.swap(a, b);
+                assert!(x.debug_validate());
+
+                x.mask = !x.mask;
+                assert!(!x.debug_validate());
+            }
+        }
+    }
+
+    #[test]
+    fn find_gte_ref() {
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Big([u64; 32], String);
+        let big = |i: u64| Big([i; 32], i.to_string());
+
+        let x = OrderedCollection::from((0..100).map(|i| big(2 * i)).collect::<Vec<_>>());
+        for i in 0..200 {
+            let q = big(i);
+            let expected = if i <= 198 { Some(big(i + i % 2)) } else { None };
+            assert_eq!(x.find_gte_ref(&q), expected.as_ref());
+            // the query is still ours
+            assert_eq!(q, big(i));
+        }
+
+        let x = OrderedCollection::from(vec![String::from("a"), String::from("c")]);
+        assert_eq!(x.find_gte_ref("b").map(String::as_str), Some("c"));
+        assert_eq!(x.find_gte_ref("d"), None);
+    }
+
+    #[test]
+    fn capacity_for() {
+        for &n in &[0, 1, 2, 3, 7, 8, 9, 100, 1023, 1024] {
+            let x = OrderedCollection::from_sorted_iter(0..n);
+            assert_eq!(OrderedCollection::<usize>::capacity_for(n), n);
+            assert_eq!(
+                x.items.capacity(),
+                OrderedCollection::<usize>::capacity_for(n)
+            );
+            assert_eq!(
+                x.to_eytzinger_vec().len(),
+                OrderedCollection::<usize>::capacity_for(n)
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn find_gte_par() {
+        for n in 0..sizes(100) {
+            let x = OrderedCollection::from((0..n).map(|i| 2 * i).collect::<Vec<_>>());
+            let xs: Vec<_> = (0..5000).map(|i| i % (2 * n + 2)).collect();
+            let serial: Vec<_> = xs.iter().map(|&q| x.find_gte(q)).collect();
+            assert_eq!(x.find_gte_par(&xs), serial);
+        }
+    }
+
+    #[test]
+    fn range() {
+        use std::ops::Bound::{Excluded, Included, Unbounded};
+        use std::ops::RangeBounds;
+
+        for n in 0..sizes(40) {
+            let v: Vec<_> = (0..n).map(|i| 2 * i + 1).collect();
+            let x = OrderedCollection::from(v.clone());
+            for lo in 0..2 * n + 2 {
+                // include some ranges whose start is past their end
+                for hi in lo.saturating_sub(2)..2 * n + 3 {
+                    for &start in &[Included(lo), Excluded(lo), Unbounded] {
+                        for &end in &[Included(hi), Excluded(hi), Unbounded] {
+                            let r = (start, end);
+                            let expected: Vec<_> = v.iter().filter(|e| r.contains(*e)).collect();
+                            let got = x.range(r);
+                            assert_eq!(got.len(), expected.len());
+                            assert_eq!(got.collect::<Vec<_>>(), expected);
+                        }
+                    }
+                }
+            }
+
+            let all: Vec<_> = v.iter().collect();
+            assert_eq!(x.iter().collect::<Vec<_>>(), all);
+            let from: Vec<_> = v.iter().filter(|&&e| e >= n).collect();
+            assert_eq!(x.range_from(n).collect::<Vec<_>>(), from);
+        }
+    }
+
+    #[test]
+    fn sorted_iter_in_struct() {
+        struct Wrapper<'a> {
+            rest: SortedIter<'a, u32>,
         }
-        for i in 33..64 {
-            assert_eq!(x.find_gte(i), Some(&64));
+
+        impl<'a> Wrapper<'a> {
+            fn new(x: &'a OrderedCollection<u32>) -> Self {
+                Wrapper { rest: x.range(2..) }
+            }
         }
-        assert_eq!(x.find_gte(65), None);
+
+        let x = OrderedCollection::from(vec![3, 1, 2, 5]);
+        let mut w = Wrapper::new(&x);
+        assert_eq!(w.rest.next(), Some(&2));
+        assert_eq!(w.rest.len(), 2);
+        assert_eq!(w.rest.collect::<Vec<_>>(), [&3, &5]);
     }
 
     #[test]
-    fn unbalanced_exact() {
-        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64, 128, 256]);
-        assert_eq!(x.find_gte(1), Some(&1));
-        assert_eq!(x.find_gte(2), Some(&2));
-        assert_eq!(x.find_gte(4), Some(&4));
-        assert_eq!(x.find_gte(8), Some(&8));
-        assert_eq!(x.find_gte(16), Some(&16));
-        assert_eq!(x.find_gte(32), Some(&32));
-        assert_eq!(x.find_gte(64), Some(&64));
-        assert_eq!(x.find_gte(128), Some(&128));
-        assert_eq!(x.find_gte(256), Some(&256));
+    fn find_within() {
+        let x = OrderedCollection::from(vec![10u32, 20, 30]);
+        // exactly eps away on either side
+        assert_eq!(x.find_within(15, 5), Some(&10));
+        assert_eq!(x.find_within(35, 5), Some(&30));
+        assert_eq!(x.find_within(5, 5), Some(&10));
+        assert_eq!(x.find_within(36, 5), None);
+        assert_eq!(x.find_within(4, 5), None);
+        assert_eq!(x.find_within(16, 5), Some(&20));
+        assert_eq!(x.find_within(20, 0), Some(&20));
+        assert_eq!(x.find_within(21, 0), None);
+
+        let empty = OrderedCollection::<u32>::from(vec![]);
+        assert_eq!(empty.find_within(0, 100), None);
+
+        for n in 0..sizes(50) {
+            let v: Vec<u32> = (0..n as u32).map(|i| 3 * i).collect();
+            let x = OrderedCollection::from(v.clone());
+            for q in 0..3 * n as u32 + 3 {
+                for eps in 0..3 {
+                    let expected = v
+                        .iter()
+                        .filter(|&&e| e.max(q) - e.min(q) <= eps)
+                        .min_by_key(|&&e| (e.max(q) - e.min(q), e));
+                    assert_eq!(x.find_within(q, eps), expected);
+                }
+            }
+        }
     }
 
     #[test]
-    fn unbalanced_approximate() {
-        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64, 128, 256]);
-        assert_eq!(x.find_gte(0), Some(&1));
-        assert_eq!(x.find_gte(3), Some(&4));
-        assert_eq!(x.find_gte(5), Some(&8));
-        assert_eq!(x.find_gte(6), Some(&8));
-        assert_eq!(x.find_gte(7), Some(&8));
-        for i in 9..16 {
-            assert_eq!(x.find_gte(i), Some(&16));
+    fn split_at_rank() {
+        let (a, b) = OrderedCollection::from(vec![1, 2, 3, 4]).split_at_rank(2);
+        assert!(a == OrderedCollection::from(vec![1, 2]));
+        assert!(b == OrderedCollection::from(vec![3, 4]));
+
+        for n in 0..sizes(30) {
+            for k in 0..n + 2 {
+                let (a, b) = OrderedCollection::from_sorted_iter(0..n).split_at_rank(k);
+                assert!(a == OrderedCollection::from_sorted_iter(0..k.min(n)));
+                assert!(b == OrderedCollection::from_sorted_iter(k.min(n)..n));
+            }
         }
-        for i in 17..32 {
-            assert_eq!(x.find_gte(i), Some(&32));
+    }
+
+    #[test]
+    #[cfg(feature = "tracing")]
+    fn find_gte_tracing() {
+        use std::fmt;
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata};
+
+        #[derive(Default)]
+        struct Lookup {
+            levels: Option<u64>,
+            exact: Option<bool>,
         }
-        for i in 33..64 {
-            assert_eq!(x.find_gte(i), Some(&64));
+
+        impl Visit for Lookup {
+            fn record_u64(&mut self, field: &Field, value: u64) {
+                if field.name() == "levels" {
+                    self.levels = Some(value);
+                }
+            }
+
+            fn record_bool(&mut self, field: &Field, value: bool) {
+                if field.name() == "exact" {
+                    self.exact = Some(value);
+                }
+            }
+
+            fn record_debug(&mut self, _: &Field, _: &dyn fmt::Debug) {}
         }
-        for i in 65..128 {
-            assert_eq!(x.find_gte(i), Some(&128));
+
+        struct Collect(Arc<Mutex<Vec<(u64, bool)>>>);
+
+        impl tracing::Subscriber for Collect {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, _: &Attributes<'_>) -> Id {
+                Id::from_u64(1)
+            }
+
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+            fn event(&self, event: &Event<'_>) {
+                let mut l = Lookup::default();
+                event.record(&mut l);
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((l.levels.unwrap(), l.exact.unwrap()));
+            }
+
+            fn enter(&self, _: &Id) {}
+
+            fn exit(&self, _: &Id) {}
+        }
+
+        // a complete tree of depth 3, so every descent goes through all three levels
+        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+        let events = Arc::new(Mutex::new(Vec::new()));
+        tracing::subscriber::with_default(Collect(events.clone()), || {
+            assert_eq!(x.find_gte(4), Some(&4));
+            assert_eq!(x.find_gte(5), Some(&8));
+            assert_eq!(x.find_gte(65), None);
+        });
+        assert_eq!(*events.lock().unwrap(), [(3, true), (3, false), (3, false)]);
+
+        // with an incomplete bottom level, some descents end a level early
+        let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+        events.lock().unwrap().clear();
+        tracing::subscriber::with_default(Collect(events.clone()), || {
+            assert_eq!(x.find_gte(0), Some(&1));
+            assert_eq!(x.find_gte(9), None);
+        });
+        assert_eq!(*events.lock().unwrap(), [(3, false), (2, false)]);
+    }
+
+    #[test]
+    fn descend_tracking() {
+        for n in 0..sizes(200) {
+            // every element is duplicated, and queries fall on, between, and around the elements
+            let x = OrderedCollection::from((0..n).map(|i| 2 * (i / 2)).collect::<Vec<_>>());
+            for q in 0..n + 3 {
+                assert_eq!(
+                    x.descend_tracking(0, |e| q <= *e),
+                    x.descend(0, |e| q <= *e),
+                );
+                assert_eq!(x.descend_tracking(0, |e| q < *e), x.descend(0, |e| q < *e));
+            }
+        }
+    }
+
+    #[test]
+    fn drain() {
+        for n in 0..sizes(50) {
+            let mut x = OrderedCollection::from((0..n).rev().collect::<Vec<_>>());
+            let drained = x.drain();
+            assert_eq!(drained.len(), n);
+            assert_eq!(drained.collect::<Vec<_>>(), (0..n).collect::<Vec<_>>());
+            assert_eq!(x.len(), 0);
+            assert_eq!(x.find_gte(0), None);
+
+            // the emptied collection can be refilled
+            x.extend_from_sorted(0..n);
+            assert!(x == OrderedCollection::from_sorted_iter(0..n));
+        }
+    }
+
+    #[test]
+    fn keyed() {
+        // the payload is deliberately not comparable
+        struct Record {
+            id: String,
+            payload: Vec<f64>,
+        }
+
+        impl Keyed for Record {
+            type Key = String;
+            fn key(&self) -> &String {
+                &self.id
+            }
+        }
+
+        let record = |id: &str| Record {
+            id: id.to_string(),
+            payload: vec![f64::NAN],
+        };
+        let x = OrderedCollectionByKey::from(vec![record("d"), record("b"), record("f")]);
+        assert_eq!(x.len(), 3);
+        assert_eq!(x.find_gte("a").map(|r| &*r.id), Some("b"));
+        assert_eq!(x.find_gte("b").map(|r| &*r.id), Some("b"));
+        assert_eq!(x.find_gte(&String::from("c")).map(|r| &*r.id), Some("d"));
+        assert_eq!(x.find_gte("e").map(|r| r.payload.len()), Some(1));
+        assert!(x.find_gte("g").is_none());
+        assert!(OrderedCollectionByKey::<Record>::new(vec![]).is_empty());
+    }
+
+    #[test]
+    fn contains_range() {
+        let x = OrderedCollection::from(vec![10, 20, 30, 40]);
+        // empty
+        assert!(!x.contains_range(11, 20));
+        assert!(!x.contains_range(20, 20));
+        assert!(!x.contains_range(30, 20));
+        assert!(!x.contains_range(41, 100));
+        assert!(!x.contains_range(0, 10));
+        // one element
+        assert!(x.contains_range(20, 21));
+        assert!(x.contains_range(11, 21));
+        assert!(x.contains_range(0, 11));
+        assert!(x.contains_range(40, 100));
+        // many elements
+        assert!(x.contains_range(0, 100));
+        assert!(x.contains_range(15, 35));
+
+        let empty = OrderedCollection::<u32>::from(vec![]);
+        assert!(!empty.contains_range(0, 100));
+    }
+
+    #[test]
+    fn clear() {
+        for n in 0..sizes(50) {
+            let mut x = OrderedCollection::from((0..n).collect::<Vec<_>>());
+            let cap = x.capacity();
+            x.clear();
+            assert!(x.is_empty());
+            assert_eq!(x.capacity(), cap);
+            assert_eq!(x.find_gte(0), None);
+            assert!(x.debug_validate());
+
+            // a rebuild through a Rebuilder reuses the allocation
+            let mut r = Rebuilder::new();
+            r.recycle(x);
+            let y = r.rebuild_from_sorted(0..n);
+            assert_eq!(y.capacity(), cap);
+            assert_eq!(y.find_gte(0), if n == 0 { None } else { Some(&0) });
+        }
+    }
+
+    #[test]
+    fn from_sorted_iter_dedup() {
+        for n in 0..sizes(50) {
+            // run lengths of 1, 2, 3, 1, 2, 3, ...
+            let v: Vec<_> = (0..n)
+                .flat_map(|i| std::iter::repeat(i).take(i % 3 + 1))
+                .collect();
+            let x = OrderedCollection::from_sorted_iter_dedup(v.iter().copied());
+            assert_eq!(x.len(), n);
+            assert!(x == OrderedCollection::from_sorted_iter(0..n));
+            assert!(x == OrderedCollection::from_dedup(v));
+        }
+    }
+
+    #[test]
+    fn find_gte_counting() {
+        for n in 0..sizes(300) {
+            let x = OrderedCollection::from((0..n).map(|i| 2 * i).collect::<Vec<_>>());
+            let depth = x.depth() as usize;
+            for q in 0..2 * n + 2 {
+                let (found, comparisons) = x.find_gte_counting(q);
+                assert_eq!(found, x.find_gte(q));
+                assert!(comparisons == depth || comparisons + 1 == depth);
+            }
+        }
+
+        // every descent of a complete tree is equally long
+        for &n in &[1, 3, 7, 15, 31, 63, 127] {
+            let x = OrderedCollection::from_sorted_iter(0..n);
+            for q in 0..n + 1 {
+                assert_eq!(x.find_gte_counting(q).1, x.depth() as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn time_keys() {
+        use std::time::{Duration, Instant};
+
+        let ms = Duration::from_millis;
+        let x = OrderedCollection::from(vec![ms(30), ms(10), ms(20)]);
+        assert_eq!(x.find_gte(ms(0)), Some(&ms(10)));
+        assert_eq!(x.find_gte(ms(10)), Some(&ms(10)));
+        assert_eq!(x.find_gte(Duration::from_micros(10_001)), Some(&ms(20)));
+        assert_eq!(x.find_gt(ms(20)), Some(&ms(30)));
+        assert_eq!(x.find_gte(ms(31)), None);
+
+        let base = Instant::now();
+        let x = OrderedCollection::from(vec![base + ms(30), base + ms(10), base + ms(20)]);
+        assert_eq!(x.find_gte(base), Some(&(base + ms(10))));
+        assert_eq!(x.find_gte(base + ms(11)), Some(&(base + ms(20))));
+        assert_eq!(x.find_gte(base + ms(31)), None);
+
+        // an instant can also be found by its offset from a base instant
+        let since = |e: &Instant, d: &Duration| e.duration_since(base).cmp(d);
+        assert_eq!(x.find_gte_cmp(&ms(15), since), Some(&(base + ms(20))));
+        assert_eq!(x.find_gte_cmp(&ms(40), since), None);
+    }
+
+    #[test]
+    fn as_ref_collection() {
+        for n in 0..sizes(100) {
+            let x = OrderedCollection::from((0..n).map(|i| 2 * i).collect::<Vec<_>>());
+            let r = x.as_ref_collection();
+            assert_eq!(r.len(), n);
+            assert!(r.debug_validate());
+            for q in 0..2 * n + 2 {
+                assert_eq!(r.find_gte_deref(q), x.find_gte(q));
+            }
+        }
+
+        // the prefetch settings carry over to the view
+        let mut x = OrderedCollection::builder()
+            .prefetch_offset(5)
+            .build((0..100).collect::<Vec<_>>());
+        x.set_prefetch_enabled(false);
+        let r = x.as_ref_collection();
+        assert_eq!(r.offset, 5);
+        assert!(!r.prefetch);
+    }
+
+    #[test]
+    fn find_gte_copy() {
+        for n in 0..sizes(100) {
+            let x = OrderedCollection::from((0..n as u32).map(|i| 2 * (i / 2)).collect::<Vec<_>>());
+            for q in 0..n as u32 + 2 {
+                assert_eq!(x.find_gte_copy(q), x.find_gte(q));
+            }
+        }
+    }
+
+    #[test]
+    fn shared_pointers() {
+        use std::rc::Rc;
+        use std::sync::Arc;
+
+        let v: Vec<Arc<u32>> = (0..100).rev().map(|i| Arc::new(2 * i)).collect();
+        let x = OrderedCollection::from(v.clone());
+        for q in 0..202u32 {
+            let expected = if q < 199 { Some(q + q % 2) } else { None };
+            assert_eq!(x.find_gte(q).map(|v| **v), expected);
+            assert_eq!(x.find_gte_ref(&q).map(|v| **v), expected);
+        }
+        // the collection shares the elements rather than copying them
+        assert!(Arc::ptr_eq(x.find_gte(0).unwrap(), v.last().unwrap()));
+
+        let x =
+            OrderedCollection::from(vec![Rc::new(String::from("b")), Rc::new(String::from("a"))]);
+        assert_eq!(x.find_gte(String::from("a")).map(|v| v.as_str()), Some("a"));
+        assert_eq!(
+            x.find_gte_ref(&String::from("aa")).map(|v| v.as_str()),
+            Some("b")
+        );
+    }
+
+    #[test]
+    fn from_sorted_iter_hint() {
+        for n in 0..sizes(50) {
+            let expected = OrderedCollection::from_sorted_iter(0..2 * n);
+
+            // exact hint
+            let iter = (0..n).chain(n..2 * n);
+            assert_eq!(iter.size_hint(), (2 * n, Some(2 * n)));
+            assert!(OrderedCollection::from_sorted_iter_hint(iter) == expected);
+
+            // unknown hint
+            let iter = (0..4 * n).filter(|i| i % 2 == 0).map(|i| i / 2);
+            assert_eq!(iter.size_hint(), (0, Some(4 * n)));
+            assert!(OrderedCollection::from_sorted_iter_hint(iter) == expected);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_sorted_iter_hint_lying() {
+        struct Liar(std::ops::Range<u32>);
+
+        impl Iterator for Liar {
+            type Item = u32;
+            fn next(&mut self) -> Option<u32> {
+                self.0.next()
+            }
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.0.len() + 1, Some(self.0.len() + 1))
+            }
+        }
+
+        let _ = OrderedCollection::from_sorted_iter_hint(Liar(0..4));
+    }
+
+    #[test]
+    fn push_sorted() {
+        for n in 0..sizes(100) {
+            let mut b = OrderedCollection::with_capacity(n);
+            for i in 0..n {
+                b.push_sorted(2 * i);
+            }
+            assert!(b.finish() == OrderedCollection::from_sorted_iter((0..n).map(|i| 2 * i)));
+        }
+
+        // zero-sized elements
+        let mut b = OrderedCollection::with_capacity(3);
+        for _ in 0..3 {
+            b.push_sorted(());
+        }
+        assert_eq!(b.finish().len(), 3);
+    }
+
+    #[test]
+    fn push_sorted_drops_partial() {
+        use std::rc::Rc;
+
+        let e = Rc::new(());
+        for n in 0..sizes(20) {
+            for pushed in 0..n + 1 {
+                let mut b = OrderedCollection::with_capacity(n);
+                for _ in 0..pushed {
+                    b.push_sorted(e.clone());
+                }
+                assert_eq!(Rc::strong_count(&e), pushed + 1);
+                if pushed == n {
+                    let x: OrderedCollection<_> = b.finish();
+                    assert_eq!(Rc::strong_count(&e), n + 1);
+                    drop(x);
+                } else {
+                    drop(b);
+                }
+                assert_eq!(Rc::strong_count(&e), 1);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "pushed more elements than the builder has capacity for")]
+    fn push_sorted_too_many() {
+        let mut b = OrderedCollection::with_capacity(1);
+        b.push_sorted(1);
+        b.push_sorted(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "pushed fewer elements than the builder has capacity for")]
+    fn push_sorted_too_few() {
+        let mut b = OrderedCollection::with_capacity(2);
+        b.push_sorted(1);
+        let _ = b.finish();
+    }
+
+    #[test]
+    fn raw_parts_round_trip() {
+        for n in 0..sizes(100) {
+            let x = OrderedCollection::from((0..n as u32).rev().collect::<Vec<_>>());
+
+            // a length-prefixed encoding of the layout
+            let (items, len) = x.raw_parts();
+            assert_eq!(len, n);
+            let mut bytes = (len as u64).to_le_bytes().to_vec();
+            for e in items {
+                bytes.extend_from_slice(&e.to_le_bytes());
+            }
+
+            let mut len = [0; 8];
+            len.copy_from_slice(&bytes[..8]);
+            let len = u64::from_le_bytes(len) as usize;
+            let items: Vec<_> = bytes[8..]
+                .chunks(4)
+                .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+                .collect();
+            // safe because the parts were encoded from a valid collection
+            let y = unsafe { OrderedCollection::from_raw_parts(items, len) };
+            assert!(x == y);
+            for q in 0..n as u32 + 1 {
+                assert_eq!(y.find_gte(q), x.find_gte(q));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_raw_parts_wrong_len() {
+        let x = OrderedCollection::from(vec![1, 2, 3]);
+        let (items, n) = x.raw_parts();
+        // safe because the elements come straight from another collection
+        let _ = unsafe { OrderedCollection::from_raw_parts(items.to_vec(), n + 1) };
+    }
+
+    #[test]
+    fn find_gte_budgeted() {
+        let x = OrderedCollection::from_sorted_iter(0..4096u32);
+        assert_eq!(x.depth(), 13);
+        assert_eq!(x.find_gte_budgeted(5, 4), Err(BudgetExceeded { depth: 13 }));
+        assert_eq!(
+            x.find_gte_budgeted(5, 12),
+            Err(BudgetExceeded { depth: 13 })
+        );
+        assert_eq!(x.find_gte_budgeted(5, 13), Ok(Some(&5)));
+        assert_eq!(x.find_gte_budgeted(4096, usize::MAX), Ok(None));
+        assert_eq!(
+            BudgetExceeded { depth: 13 }.to_string(),
+            "lookup may take up to 13 steps, which exceeds its budget"
+        );
+
+        let empty = OrderedCollection::<u32>::from(vec![]);
+        assert_eq!(empty.find_gte_budgeted(5, 0), Ok(None));
+    }
+
+    #[test]
+    fn shrink_to() {
+        let mut r = Rebuilder::new();
+        r.recycle(OrderedCollection::from_sorted_iter(0..1000));
+        for &n in &[0, 10, 100] {
+            for &k in &[0, 5, 50, 500, 5000] {
+                let mut x = r.rebuild_from_sorted(0..n);
+                assert!(x.capacity() >= 1000);
+                x.shrink_to(k);
+                assert!(x.capacity() >= n.max(k).min(1000));
+                assert!(x.capacity() <= 1000);
+                assert!(x == OrderedCollection::from_sorted_iter(0..n));
+                r.recycle(x);
+                r.recycle(OrderedCollection::from_sorted_iter(0..1000));
+            }
+        }
+
+        let mut x = OrderedCollection::from_sorted_iter(0..100);
+        x.clear();
+        x.shrink_to_fit();
+        assert_eq!(x.capacity(), 0);
+    }
+
+    #[test]
+    fn find_gte_sorted_index() {
+        for n in 0..sizes(100) {
+            // with duplicates, the index is that of the first equal element
+            let sorted: Vec<_> = (0..n).map(|i| 2 * (i / 2)).collect();
+            let x = OrderedCollection::from(sorted.clone());
+            for q in 0..n + 2 {
+                let expected = sorted.partition_point(|&e| e < q);
+                let expected = if expected < n { Some(expected) } else { None };
+                assert_eq!(x.find_gte_sorted_index(q), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn append() {
+        use std::collections::BTreeSet;
+
+        for n in 0..sizes(30) {
+            for m in 0..sizes(30) {
+                let a: Vec<_> = (0..n).map(|i| 3 * i).collect();
+                let b: Vec<_> = (0..m).map(|i| 2 * i).collect();
+
+                let mut x = OrderedCollection::from(a.clone());
+                let mut y = OrderedCollection::from(b.clone());
+                x.append(&mut y);
+
+                let mut xs: BTreeSet<_> = a.iter().copied().collect();
+                let mut ys: BTreeSet<_> = b.iter().copied().collect();
+                xs.append(&mut ys);
+
+                // like BTreeSet::append, other ends up empty
+                assert!(y.is_empty());
+                assert!(ys.is_empty());
+                // unlike it, duplicates are kept
+                assert_eq!(x.len(), n + m);
+                let mut all = [a, b].concat();
+                all.sort();
+                assert!(x == OrderedCollection::from_sorted_iter(all));
+                assert!(x.eq_as_set(&OrderedCollection::from_sorted_iter(xs)));
+            }
+        }
+    }
+
+    #[test]
+    fn transparent_newtype() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+        #[repr(transparent)]
+        struct Id(u32);
+
+        for n in 0..sizes(100) as u32 {
+            let raw = OrderedCollection::from((0..n).map(|i| 2 * i).collect::<Vec<_>>());
+            let ids = OrderedCollection::from((0..n).map(|i| Id(2 * i)).collect::<Vec<_>>());
+            // the newtype does not change the layout
+            assert_eq!(
+                ids.to_eytzinger_vec()
+                    .iter()
+                    .map(|id| id.0)
+                    .collect::<Vec<_>>(),
+                raw.to_eytzinger_vec()
+            );
+            for q in 0..2 * n + 2 {
+                assert_eq!(ids.find_gte(Id(q)).map(|id| id.0), raw.find_gte(q).copied());
+            }
+        }
+    }
+
+    #[test]
+    fn is_sorted_ascending() {
+        assert!(OrderedCollection::is_sorted_ascending(vec![1, 2, 2, 3, 10]));
+        assert!(OrderedCollection::is_sorted_ascending(&[1, 2, 3]));
+        assert!(!OrderedCollection::is_sorted_ascending(vec![1, 3, 2]));
+        assert!(!OrderedCollection::is_sorted_ascending(vec![2, 1, 3]));
+        assert!(OrderedCollection::is_sorted_ascending(Vec::<u32>::new()));
+        assert!(OrderedCollection::is_sorted_ascending(vec![42]));
+        assert!(OrderedCollection::is_sorted_ascending(
+            (0..1000).map(|i| i / 3)
+        ));
+    }
+
+    #[test]
+    fn ordered_strings() {
+        let words = [
+            "",
+            "a",
+            "ab",
+            "abc",
+            "abd",
+            "b",
+            "ba",
+            "cafe",
+            "caf\u{e9}",
+            "zebra",
+            "zz",
+        ];
+        for n in 0..=words.len() {
+            let v: Vec<String> = words[..n].iter().rev().map(|w| w.to_string()).collect();
+            let x = OrderedStrings::from(v);
+            assert_eq!(x.len(), n);
+            assert_eq!(x.is_empty(), n == 0);
+
+            // exact strings, and strings in between them
+            for q in words
+                .iter()
+                .chain(&["aa", "abcd", "c", "caff", "zzz", "\u{10ffff}"])
+            {
+                let expect = words[..n].iter().find(|w| *w >= q).cloned();
+                assert_eq!(x.find_gte(q), expect, "n = {}, q = {:?}", n, q);
+            }
+        }
+    }
+
+    #[test]
+    fn ordered_strings_prefix() {
+        let v = [
+            "apple",
+            "application",
+            "apply",
+            "banana",
+            "band",
+            "bandana",
+            "can",
+        ];
+        let x = OrderedStrings::new(v.iter().map(|s| s.to_string()).collect());
+        for (prefix, first) in &[
+            ("app", Some("apple")),
+            ("appli", Some("application")),
+            ("ban", Some("banana")),
+            ("band", Some("band")),
+            ("banda", Some("bandana")),
+            ("bx", None),
+            ("d", None),
+        ] {
+            let found = x.find_gte(prefix).filter(|s| s.starts_with(prefix));
+            assert_eq!(found, *first, "prefix {:?}", prefix);
+        }
+        assert_eq!(x.find_gte("bx"), Some("can"));
+    }
+
+    #[test]
+    fn layout_info() {
+        for n in 0..sizes(300) {
+            let x = OrderedCollection::from((0..n as u16).collect::<Vec<_>>());
+            let info = x.layout_info();
+            assert_eq!(info.len, n);
+            assert_eq!(info.slots, OrderedCollection::<u16>::capacity_for(n));
+            assert_eq!(info.align, 2);
+        }
+
+        // spare capacity is not part of the layout
+        let mut x = OrderedCollection::from(vec![[0u8; 3]; 10]);
+        x.clear();
+        assert!(x.capacity() >= 10);
+        assert_eq!(
+            x.layout_info(),
+            LayoutInfo {
+                len: 0,
+                slots: 0,
+                align: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn find_gte_first_equal() {
+        // ordered by the key alone, so that equal elements can still be told apart
+        #[derive(Debug, Clone, Copy)]
+        struct Dup {
+            key: u32,
+            rank: usize,
+        }
+        impl PartialEq for Dup {
+            fn eq(&self, other: &Self) -> bool {
+                self.key == other.key
+            }
+        }
+        impl Eq for Dup {}
+        impl PartialOrd for Dup {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Dup {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.key.cmp(&other.key)
+            }
+        }
+
+        for n in 0..sizes(300) {
+            for run in 1..5 {
+                let v: Vec<_> = (0..n)
+                    .map(|rank| Dup {
+                        key: (rank / run) as u32,
+                        rank,
+                    })
+                    .collect();
+                let x = OrderedCollection::from_sorted_iter(v);
+                for key in 0..(n / run + 1) as u32 {
+                    let q = Dup {
+                        key,
+                        rank: usize::MAX,
+                    };
+                    let found = x.find_gte(q).map(|d| d.rank);
+                    let first = (0..n).find(|&r| (r / run) as u32 >= key);
+                    assert_eq!(found, first, "n = {}, run = {}, key = {}", n, run, key);
+                    if let Some(r) = first {
+                        assert!(std::ptr::eq(x.find_gte(q).unwrap(), &x[r]));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn try_from_sorted_results() {
+        use std::rc::Rc;
+
+        for n in 0..sizes(100) {
+            let ok = (0..n).map(Ok::<_, ()>);
+            let x = OrderedCollection::try_from_sorted_results(ok, n).unwrap();
+            assert_eq!(x.len(), n);
+            assert!(x.debug_validate());
+            assert!(x.iter().copied().eq(0..n));
+
+            // only the first `len` items are used
+            let x = OrderedCollection::try_from_sorted_results((0..).map(Ok::<_, ()>), n).unwrap();
+            assert!(x.iter().copied().eq(0..n));
+        }
+
+        // an error at every position, with elements whose drops can be counted
+        let token = Rc::new(());
+        for n in 1..sizes(100) {
+            for bad in 0..n {
+                let mut pulled = 0;
+                let iter = (0..n).map(|i| {
+                    pulled += 1;
+                    if i == bad {
+                        Err(i)
+                    } else {
+                        Ok((i, Rc::clone(&token)))
+                    }
+                });
+                let r = OrderedCollection::try_from_sorted_results(iter, n);
+                assert_eq!(r.err(), Some(bad));
+                assert_eq!(pulled, bad + 1);
+                assert_eq!(Rc::strong_count(&token), 1);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "fewer elements")]
+    fn try_from_sorted_results_short() {
+        let _ = OrderedCollection::try_from_sorted_results((0..5).map(Ok::<_, ()>), 10);
+    }
+
+    #[test]
+    fn mutation_keeps_prefetch_settings() {
+        let check = |x: &OrderedCollection<u32>| {
+            assert!(!x.prefetch_enabled());
+            assert_eq!(x.offset, 5);
+        };
+        let mut x = OrderedCollection::builder()
+            .prefetch_offset(5)
+            .build((0..100).collect::<Vec<_>>());
+        x.set_prefetch_enabled(false);
+
+        x.retain(|&v| v % 2 == 0);
+        check(&x);
+        x.extend_from_sorted(vec![1, 3, 5]);
+        check(&x);
+        let mut other = OrderedCollection::from(vec![7, 9]);
+        x.append(&mut other);
+        check(&x);
+        let tail = x.split_off(50);
+        check(&x);
+        check(&tail);
+        let (a, b) = tail.split_at_rank(3);
+        check(&a);
+        check(&b);
+        assert!(a.debug_validate() && b.debug_validate() && x.debug_validate());
+        let _ = x.drain();
+        check(&x);
+        // the settings of the other collection are kept as well
+        assert!(other.prefetch_enabled() && other.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "allocator_api")]
+    fn allocator_global() {
+        use std::alloc::Global;
+
+        for n in 0..sizes(100) {
+            let expected =
+                OrderedCollection::from((0..n).map(|i| (i * 37) % 101).collect::<Vec<_>>());
+
+            let mut v = Vec::new_in(Global);
+            v.extend((0..n).map(|i| (i * 37) % 101));
+            let x: OrderedCollection<usize, Global> = OrderedCollection::from_vec_in(v);
+            assert_eq!(x.len(), n);
+            assert_eq!(x.is_empty(), n == 0);
+            for q in 0..102 {
+                assert_eq!(x.find_gte(q), expected.find_gte(q));
+            }
+            // with the global allocator, all the other methods are available as well
+            assert!(x == expected);
+            assert!(x.debug_validate());
+
+            let y = OrderedCollection::from_sorted_iter_in(expected.iter().copied(), Global);
+            assert!(y == expected);
         }
-        for i in 129..256 {
-            assert_eq!(x.find_gte(i), Some(&256));
+    }
+
+    #[test]
+    #[cfg(feature = "allocator_api")]
+    fn allocator_custom() {
+        use std::alloc::{AllocError, Allocator, Global, Layout};
+        use std::cell::Cell;
+        use std::ptr::NonNull;
+
+        // counts the allocations it hands out, and otherwise defers to the global allocator
+        struct Counting<'a>(&'a Cell<usize>);
+        unsafe impl<'a> Allocator for Counting<'a> {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                self.0.set(self.0.get() + 1);
+                Global.allocate(layout)
+            }
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                unsafe { Global.deallocate(ptr, layout) }
+            }
         }
-        assert_eq!(x.find_gte(257), None);
+
+        let count = Cell::new(0);
+        let x =
+            OrderedCollection::from_sorted_iter_in((0..100u32).map(|i| 2 * i), Counting(&count));
+        assert!(count.get() > 0);
+        assert_eq!(x.len(), 100);
+        assert_eq!(x.find_gte(0), Some(&0));
+        assert_eq!(x.find_gte(51), Some(&52));
+        assert_eq!(x.find_gte(199), None);
+        assert_eq!(x.allocator().0.get(), count.get());
     }
 }
 
@@ -457,6 +6543,39 @@ mod b {
         dup_usize(i) as u32
     }
 
+    // larger element types, ordered by a leading key, to show how performance varies with `T`.
+    macro_rules! padded {
+        ($name:ident, $bytes:expr, $nodup:ident, $dup:ident) => {
+            #[derive(PartialEq, Eq, PartialOrd, Ord)]
+            #[repr(C)]
+            pub struct $name {
+                key: usize,
+                payload: [u8; $bytes - 8],
+            }
+
+            #[inline]
+            fn $nodup(i: usize) -> $name {
+                $name {
+                    key: nodup_usize(i),
+                    payload: [0; $bytes - 8],
+                }
+            }
+
+            #[inline]
+            fn $dup(i: usize) -> $name {
+                $name {
+                    key: dup_usize(i),
+                    payload: [0; $bytes - 8],
+                }
+            }
+        }
+    }
+
+    padded!(Bytes16, 16, nodup_bytes16, dup_bytes16);
+    padded!(Bytes32, 32, nodup_bytes32, dup_bytes32);
+    padded!(Bytes128, 128, nodup_bytes128, dup_bytes128);
+    padded!(Bytes256, 256, nodup_bytes256, dup_bytes256);
+
     macro_rules! construction_benches {
         ($t:ident, $v:ident) => {
             mod $v {
@@ -504,7 +6623,7 @@ mod b {
                     let mk = concat_idents!(make_, $t);
                     let s = concat_idents!(search_, $t);
                     let mapper = concat_idents!(nodup_, $v);
-                    bench_search!(c, mk, s, mapper, b);
+                    bench_search!(c, mk, s, mapper, uniform, b);
                 }
 
                 #[bench]
@@ -526,7 +6645,7 @@ mod b {
                     let mk = concat_idents!(make_, $t);
                     let s = concat_idents!(search_, $t);
                     let mapper = concat_idents!(dup_, $v);
-                    bench_search!(c, mk, s, mapper, b);
+                    bench_search!(c, mk, s, mapper, uniform, b);
                 }
 
                 #[bench]
@@ -543,6 +6662,60 @@ mod b {
                 fn l3_dup(b: &mut Bencher) {
                     dup(Cache::L3, b);
                 }
+
+                fn skewed(c: Cache, b: &mut Bencher) {
+                    let mk = concat_idents!(make_, $t);
+                    let s = concat_idents!(search_, $t);
+                    let mapper = concat_idents!(nodup_, $v);
+                    bench_search!(c, mk, s, mapper, zipf, b);
+                }
+
+                #[bench]
+                fn l1_zipf(b: &mut Bencher) {
+                    skewed(Cache::L1, b);
+                }
+
+                #[bench]
+                fn l2_zipf(b: &mut Bencher) {
+                    skewed(Cache::L2, b);
+                }
+
+                #[bench]
+                fn l3_zipf(b: &mut Bencher) {
+                    skewed(Cache::L3, b);
+                }
+            }
+        }
+    }
+
+    // construction followed by a single lookup, as for a table that is built to answer one query.
+    // the small sizes are where this is most likely to happen, and where the cost of the layout
+    // is hardest to win back.
+    macro_rules! oneshot_benches {
+        ($t:ident, $v:ident) => {
+            mod $v {
+                use super::*;
+                fn nodup(size: usize, b: &mut Bencher) {
+                    let mk = concat_idents!(make_, $t);
+                    let s = concat_idents!(search_, $t);
+                    let mapper = concat_idents!(nodup_, $v);
+                    bench_oneshot!(size, mk, s, mapper, b);
+                }
+
+                #[bench]
+                fn n16(b: &mut Bencher) {
+                    nodup(16, b);
+                }
+
+                #[bench]
+                fn n128(b: &mut Bencher) {
+                    nodup(128, b);
+                }
+
+                #[bench]
+                fn l1(b: &mut Bencher) {
+                    nodup(Cache::L1.size(), b);
+                }
             }
         }
     }
@@ -557,11 +6730,20 @@ mod b {
                     construction_benches!($t, u32);
                     construction_benches!($t, usize);
                 }
+                mod oneshot {
+                    pub use super::*;
+                    oneshot_benches!($t, u8);
+                    oneshot_benches!($t, u32);
+                    oneshot_benches!($t, usize);
+                }
                 mod search {
                     pub use super::*;
                     search_benches!($t, u8);
                     search_benches!($t, u32);
                     search_benches!($t, usize);
+                    search_benches!($t, bytes16);
+                    search_benches!($t, bytes32);
+                    search_benches!($t, bytes128);
                 }
             }
         }
@@ -583,18 +6765,51 @@ mod b {
         }
     }
 
+    macro_rules! bench_oneshot {
+        ($size:expr, $make:ident, $search:ident, $mapper:ident, $b:ident) => {
+            let size = $size;
+            let mut v: Vec<_> = (0..size).map(&$mapper).collect();
+            let mut r = 0usize;
+
+            $b.iter(|| {
+                for e in v.iter_mut() {
+                    r = r.wrapping_mul(1664525).wrapping_add(1013904223);
+                    *e = $mapper(r % size);
+                }
+                let x = $mapper(uniform(&mut r, size));
+                let c = $make(&mut v);
+                black_box($search(&c, x).is_some());
+            });
+        }
+    }
+
+    // query distributions for bench_search, which pick the index of the next query in 0..size
+    // from the state of an LCG.
+
+    fn uniform(r: &mut usize, size: usize) -> usize {
+        // LCG constants from https://en.wikipedia.org/wiki/Numerical_Recipes.
+        *r = r.wrapping_mul(1664525).wrapping_add(1013904223);
+        // Lookup the whole range to get 50% hits and 50% misses.
+        *r % size
+    }
+
+    fn zipf(r: &mut usize, size: usize) -> usize {
+        *r = r.wrapping_mul(1664525).wrapping_add(1013904223);
+        // the low bits of the LCG are not very random, so use the high ones. index k is then
+        // picked with probability roughly proportional to 1 / (k + 1).
+        let u = (*r as u64 >> 32) as f64 / (1u64 << 32) as f64;
+        ((size as f64 + 1.0).powf(u) - 1.0) as usize
+    }
+
     macro_rules! bench_search {
-        ($cache:expr, $make:ident, $search:ident, $mapper:ident, $b:ident) => {
+        ($cache:expr, $make:ident, $search:ident, $mapper:ident, $query:ident, $b:ident) => {
             let size = $cache.size();
             let mut v: Vec<_> = (0..size).map(&$mapper).collect();
             let mut r = 0usize;
 
             let c = $make(&mut v);
             $b.iter(move || {
-                // LCG constants from https://en.wikipedia.org/wiki/Numerical_Recipes.
-                r = r.wrapping_mul(1664525).wrapping_add(1013904223);
-                // Lookup the whole range to get 50% hits and 50% misses.
-                let x = $mapper(r % size);
+                let x = $mapper($query(&mut r, size));
 
                 black_box($search(&c, x).is_some());
             });
@@ -606,7 +6821,7 @@ mod b {
     }
 
     fn search_this<'a, T: Ord>(c: &OrderedCollection<&'a T>, x: T) -> Option<&'a T> {
-        c.find_gte(x).map(|v| &**v)
+        c.find_gte_deref(x)
     }
 
     benches!(this);
@@ -635,4 +6850,420 @@ mod b {
     }
 
     benches!(sorted_vec);
+
+    fn make_partition_point<T: Ord>(v: &mut Vec<T>) -> &[T] {
+        v.sort_unstable();
+        &v[..]
+    }
+
+    fn search_partition_point<'a, T: Ord>(c: &'a &[T], x: T) -> Option<&'a T> {
+        c.get(c.partition_point(|e| *e < x))
+    }
+
+    benches!(partition_point);
+
+    fn make_partition_point_black_box<T: Ord>(v: &mut Vec<T>) -> &[T] {
+        make_partition_point(v)
+    }
+
+    // like partition_point, but with the slice and the query hidden from the optimizer, so that
+    // the search cannot be specialized to them any more than a lookup in this crate can
+    fn search_partition_point_black_box<'a, T: Ord>(c: &'a &[T], x: T) -> Option<&'a T> {
+        let (c, x) = (black_box(*c), black_box(x));
+        c.get(c.partition_point(|e| *e < x))
+    }
+
+    benches!(partition_point_black_box);
+
+    mod batch {
+        use super::*;
+
+        const QUERIES: usize = 1024;
+        #[cfg(feature = "rayon")]
+        const PAR_QUERIES: usize = 1 << 20;
+
+        fn setup() -> (OrderedCollection<u32>, Vec<u32>) {
+            setup_n(QUERIES)
+        }
+
+        fn setup_n(queries: usize) -> (OrderedCollection<u32>, Vec<u32>) {
+            let size = Cache::L3.size();
+            let c = OrderedCollection::from((0..size).map(nodup_u32).collect::<Vec<_>>());
+            let mut r = 0usize;
+            let xs = (0..queries)
+                .map(|_| {
+                    r = r.wrapping_mul(1664525).wrapping_add(1013904223);
+                    nodup_u32(r % size)
+                })
+                .collect();
+            (c, xs)
+        }
+
+        #[bench]
+        fn scalar_l3(b: &mut Bencher) {
+            let (c, xs) = setup();
+            b.iter(|| black_box(xs.iter().map(|&x| c.find_gte(x)).collect::<Vec<_>>()));
+        }
+
+        #[bench]
+        fn simd_l3(b: &mut Bencher) {
+            let (c, xs) = setup();
+            b.iter(|| black_box(c.find_gte_many(&xs)));
+        }
+
+        #[bench]
+        #[cfg(feature = "rayon")]
+        fn par_l3(b: &mut Bencher) {
+            let (c, xs) = setup_n(PAR_QUERIES);
+            b.iter(|| black_box(c.find_gte_par(&xs)));
+        }
+
+        #[bench]
+        #[cfg(feature = "rayon")]
+        fn par_scalar_l3(b: &mut Bencher) {
+            let (c, xs) = setup_n(PAR_QUERIES);
+            b.iter(|| black_box(xs.iter().map(|&x| c.find_gte(x)).collect::<Vec<_>>()));
+        }
+    }
+
+    mod prefetch_toggle {
+        use super::*;
+
+        fn search(prefetch: bool, b: &mut Bencher) {
+            let size = Cache::L3.size();
+            let mut c = OrderedCollection::from((0..size).map(nodup_u32).collect::<Vec<_>>());
+            c.set_prefetch_enabled(prefetch);
+            let mut r = 0usize;
+            b.iter(|| {
+                r = r.wrapping_mul(1664525).wrapping_add(1013904223);
+                black_box(c.find_gte(nodup_u32(r % size)).is_some());
+            });
+        }
+
+        #[bench]
+        fn enabled_l3(b: &mut Bencher) {
+            search(true, b);
+        }
+
+        #[bench]
+        fn disabled_l3(b: &mut Bencher) {
+            search(false, b);
+        }
+    }
+
+    mod deep_prefetch {
+        use super::*;
+
+        // elements that span four cache lines, stored in the collection itself rather than
+        // behind references, and too many of them to fit in L3
+        fn search(mapper: fn(usize) -> Bytes256, prefetch: bool, b: &mut Bencher) {
+            let size = Cache::L3.size() / 10;
+            let mut c = OrderedCollection::from((0..size).map(mapper).collect::<Vec<_>>());
+            c.set_prefetch_enabled(prefetch);
+            let mut r = 0usize;
+            b.iter(|| {
+                r = r.wrapping_mul(1664525).wrapping_add(1013904223);
+                black_box(c.find_gte(mapper(r % size)).is_some());
+            });
+        }
+
+        #[bench]
+        fn enabled(b: &mut Bencher) {
+            search(nodup_bytes256, true, b);
+        }
+
+        #[bench]
+        fn enabled_dup(b: &mut Bencher) {
+            search(dup_bytes256, true, b);
+        }
+
+        #[bench]
+        fn disabled(b: &mut Bencher) {
+            search(nodup_bytes256, false, b);
+        }
+
+        #[bench]
+        fn disabled_dup(b: &mut Bencher) {
+            search(dup_bytes256, false, b);
+        }
+    }
+
+    mod interpolation {
+        use super::*;
+
+        fn uniform(i: usize) -> u32 {
+            nodup_u32(i)
+        }
+
+        // two dense clusters far apart, so that interpolation guesses are mostly wrong
+        fn clustered(i: usize) -> u32 {
+            if i < Cache::L3.size() / 2 {
+                nodup_u32(i)
+            } else {
+                nodup_u32(i) + (1 << 30)
+            }
+        }
+
+        fn search(mapper: fn(usize) -> u32, interpolate: bool, b: &mut Bencher) {
+            let size = Cache::L3.size();
+            let c = OrderedCollection::from((0..size).map(mapper).collect::<Vec<_>>());
+            let mut r = 0usize;
+            b.iter(|| {
+                r = r.wrapping_mul(1664525).wrapping_add(1013904223);
+                let x = mapper(r % size);
+                if interpolate {
+                    black_box(c.find_gte_interpolating(x).is_some());
+                } else {
+                    black_box(c.find_gte(x).is_some());
+                }
+            });
+        }
+
+        #[bench]
+        fn uniform_find_gte(b: &mut Bencher) {
+            search(uniform, false, b);
+        }
+
+        #[bench]
+        fn uniform_interpolating(b: &mut Bencher) {
+            search(uniform, true, b);
+        }
+
+        #[bench]
+        fn clustered_find_gte(b: &mut Bencher) {
+            search(clustered, false, b);
+        }
+
+        #[bench]
+        fn clustered_interpolating(b: &mut Bencher) {
+            search(clustered, true, b);
+        }
+    }
+
+    mod last_left {
+        use super::*;
+
+        // queries are the elements themselves, except with `miss`, where they fall between them
+        fn search(c: Cache, mapper: fn(usize) -> u32, miss: bool, tracking: bool, b: &mut Bencher) {
+            let size = c.size();
+            let c = OrderedCollection::from((0..size).map(mapper).collect::<Vec<_>>());
+            let mut r = 0usize;
+            b.iter(|| {
+                r = r.wrapping_mul(1664525).wrapping_add(1013904223);
+                let x = mapper(r % size) + miss as u32;
+                if tracking {
+                    black_box(c.descend_tracking(0, |e| x <= *e));
+                } else {
+                    black_box(c.descend(0, |e| x <= *e));
+                }
+            });
+        }
+
+        #[bench]
+        fn l1_fixup(b: &mut Bencher) {
+            search(Cache::L1, nodup_u32, false, false, b);
+        }
+
+        #[bench]
+        fn l1_tracking(b: &mut Bencher) {
+            search(Cache::L1, nodup_u32, false, true, b);
+        }
+
+        #[bench]
+        fn l3_fixup(b: &mut Bencher) {
+            search(Cache::L3, nodup_u32, false, false, b);
+        }
+
+        #[bench]
+        fn l3_tracking(b: &mut Bencher) {
+            search(Cache::L3, nodup_u32, false, true, b);
+        }
+
+        #[bench]
+        fn l1_dup_fixup(b: &mut Bencher) {
+            search(Cache::L1, dup_u32, false, false, b);
+        }
+
+        #[bench]
+        fn l1_dup_tracking(b: &mut Bencher) {
+            search(Cache::L1, dup_u32, false, true, b);
+        }
+
+        #[bench]
+        fn l1_miss_fixup(b: &mut Bencher) {
+            search(Cache::L1, nodup_u32, true, false, b);
+        }
+
+        #[bench]
+        fn l1_miss_tracking(b: &mut Bencher) {
+            search(Cache::L1, nodup_u32, true, true, b);
+        }
+
+        #[bench]
+        fn l3_miss_fixup(b: &mut Bencher) {
+            search(Cache::L3, nodup_u32, true, false, b);
+        }
+
+        #[bench]
+        fn l3_miss_tracking(b: &mut Bencher) {
+            search(Cache::L3, nodup_u32, true, true, b);
+        }
+    }
+
+    mod copy_keys {
+        use super::*;
+
+        fn search(c: Cache, copy: bool, b: &mut Bencher) {
+            let size = c.size();
+            let c = OrderedCollection::from_sorted_iter((0..size).map(nodup_u32));
+            let mut r = 0usize;
+            b.iter(|| {
+                let x = nodup_u32(uniform(&mut r, size));
+                if copy {
+                    black_box(c.find_gte_copy(x).is_some());
+                } else {
+                    black_box(c.find_gte(x).is_some());
+                }
+            });
+        }
+
+        #[bench]
+        fn l1_find_gte(b: &mut Bencher) {
+            search(Cache::L1, false, b);
+        }
+
+        #[bench]
+        fn l1_find_gte_copy(b: &mut Bencher) {
+            search(Cache::L1, true, b);
+        }
+
+        #[bench]
+        fn l2_find_gte(b: &mut Bencher) {
+            search(Cache::L2, false, b);
+        }
+
+        #[bench]
+        fn l2_find_gte_copy(b: &mut Bencher) {
+            search(Cache::L2, true, b);
+        }
+    }
+
+    mod newtype {
+        use super::*;
+
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+        #[repr(transparent)]
+        struct Id(u32);
+
+        fn raw(c: Cache, b: &mut Bencher) {
+            let size = c.size();
+            let c = OrderedCollection::from_sorted_iter((0..size).map(nodup_u32));
+            let mut r = 0usize;
+            b.iter(|| black_box(c.find_gte(nodup_u32(uniform(&mut r, size))).is_some()));
+        }
+
+        fn wrapped(c: Cache, b: &mut Bencher) {
+            let size = c.size();
+            let c = OrderedCollection::from_sorted_iter((0..size).map(|i| Id(nodup_u32(i))));
+            let mut r = 0usize;
+            b.iter(|| black_box(c.find_gte(Id(nodup_u32(uniform(&mut r, size)))).is_some()));
+        }
+
+        #[bench]
+        fn l1_u32(b: &mut Bencher) {
+            raw(Cache::L1, b);
+        }
+
+        #[bench]
+        fn l1_id(b: &mut Bencher) {
+            wrapped(Cache::L1, b);
+        }
+
+        #[bench]
+        fn l2_u32(b: &mut Bencher) {
+            raw(Cache::L2, b);
+        }
+
+        #[bench]
+        fn l2_id(b: &mut Bencher) {
+            wrapped(Cache::L2, b);
+        }
+
+        #[bench]
+        fn l3_u32(b: &mut Bencher) {
+            raw(Cache::L3, b);
+        }
+
+        #[bench]
+        fn l3_id(b: &mut Bencher) {
+            wrapped(Cache::L3, b);
+        }
+    }
+
+    // searches over far more memory than any cache or TLB covers, so that every lookup is bound
+    // by DRAM latency. these need about a gigabyte of memory and take a while to set up, so they
+    // do nothing unless ORDSEARCH_BENCH_LARGE is set.
+    mod large {
+        use super::*;
+
+        const SIZE: usize = 100_000_000;
+
+        fn enabled() -> bool {
+            std::env::var_os("ORDSEARCH_BENCH_LARGE").is_some()
+        }
+
+        #[bench]
+        fn this(b: &mut Bencher) {
+            if !enabled() {
+                return;
+            }
+            let c = OrderedCollection::from_sorted_iter((0..SIZE).map(nodup_u32));
+            let mut r = 0usize;
+            b.iter(|| black_box(c.find_gte(nodup_u32(uniform(&mut r, SIZE))).is_some()));
+        }
+
+        #[bench]
+        fn partition_point(b: &mut Bencher) {
+            if !enabled() {
+                return;
+            }
+            let c: Vec<_> = (0..SIZE).map(nodup_u32).collect();
+            let mut r = 0usize;
+            b.iter(|| {
+                let x = nodup_u32(uniform(&mut r, SIZE));
+                black_box(c.get(c.partition_point(|e| *e < x)).is_some())
+            });
+        }
+
+        #[bench]
+        fn sorted_vec(b: &mut Bencher) {
+            if !enabled() {
+                return;
+            }
+            let c: Vec<_> = (0..SIZE).map(nodup_u32).collect();
+            let mut r = 0usize;
+            b.iter(|| black_box(c.binary_search(&nodup_u32(uniform(&mut r, SIZE))).is_ok()));
+        }
+    }
+
+    mod eytzinger_construction {
+        use super::*;
+
+        fn setup() -> Vec<u32> {
+            let size = Cache::L2.size();
+            (0..size).map(nodup_u32).collect()
+        }
+
+        #[bench]
+        fn from_sorted_iter_l2(b: &mut Bencher) {
+            let v = setup();
+            b.iter(|| black_box(OrderedCollection::from_sorted_iter(v.clone())));
+        }
+
+        #[bench]
+        fn from_eytzinger_vec_l2(b: &mut Bencher) {
+            let v = OrderedCollection::from_sorted_iter(setup()).to_eytzinger_vec();
+            b.iter(|| black_box(OrderedCollection::from_eytzinger_vec(v.clone())));
+        }
+    }
 }
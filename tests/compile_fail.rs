@@ -0,0 +1,9 @@
+extern crate trybuild;
+
+// The allocator parameter shows up in the expected diagnostics.
+#[cfg(not(feature = "allocator_api"))]
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
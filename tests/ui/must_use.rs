@@ -0,0 +1,11 @@
+#![deny(unused_must_use)]
+
+extern crate ordsearch;
+
+use ordsearch::OrderedCollection;
+
+fn main() {
+    let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    x.find_gte(3);
+    OrderedCollection::from_sorted_iter(vec![1, 2, 3]);
+}